@@ -0,0 +1,9 @@
+ALTER TABLE benchmarks
+    DROP COLUMN driver,
+    DROP COLUMN dataset,
+    DROP COLUMN algorithm,
+    DROP COLUMN repeat,
+    DROP COLUMN started_at,
+    DROP COLUMN finished_at,
+    DROP COLUMN status,
+    DROP COLUMN config_hash;
\ No newline at end of file
@@ -0,0 +1,9 @@
+ALTER TABLE benchmarks
+    ADD COLUMN driver VARCHAR(256),
+    ADD COLUMN dataset VARCHAR(256),
+    ADD COLUMN algorithm VARCHAR(256),
+    ADD COLUMN repeat INT,
+    ADD COLUMN started_at TIMESTAMP,
+    ADD COLUMN finished_at TIMESTAMP,
+    ADD COLUMN status VARCHAR(32) NOT NULL DEFAULT 'pending',
+    ADD COLUMN config_hash VARCHAR(64);
@@ -0,0 +1,2 @@
+ALTER TABLE benchmarks DROP COLUMN campaign_id;
+DROP TABLE campaigns;
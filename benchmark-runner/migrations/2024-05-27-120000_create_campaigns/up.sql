@@ -0,0 +1,12 @@
+CREATE TABLE IF NOT EXISTS campaigns(
+    id SERIAL PRIMARY KEY,
+    name VARCHAR(256) NOT NULL UNIQUE,
+    labels TEXT[] NOT NULL DEFAULT '{}',
+    notes TEXT,
+    git_commit VARCHAR(64),
+    config TEXT NOT NULL,
+    started_at TIMESTAMP NOT NULL DEFAULT now(),
+    finished_at TIMESTAMP
+);
+
+ALTER TABLE benchmarks ADD COLUMN campaign_id INT REFERENCES campaigns(id);
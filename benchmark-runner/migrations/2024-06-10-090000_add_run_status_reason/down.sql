@@ -0,0 +1 @@
+ALTER TABLE benchmarks DROP COLUMN status_reason;
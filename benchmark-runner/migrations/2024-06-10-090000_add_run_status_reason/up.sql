@@ -0,0 +1 @@
+ALTER TABLE benchmarks ADD COLUMN status_reason TEXT;
@@ -0,0 +1,2 @@
+ALTER TABLE benchmarks DROP COLUMN tainted;
+DROP TABLE run_events;
@@ -0,0 +1,11 @@
+CREATE TABLE IF NOT EXISTS run_events(
+    id SERIAL PRIMARY KEY,
+    run_id INT NOT NULL REFERENCES benchmarks(id),
+    pod VARCHAR(256) NOT NULL,
+    kind VARCHAR(32) NOT NULL,
+    reason VARCHAR(256),
+    message TEXT,
+    occurred_at TIMESTAMP NOT NULL
+);
+
+ALTER TABLE benchmarks ADD COLUMN tainted BOOLEAN NOT NULL DEFAULT FALSE;
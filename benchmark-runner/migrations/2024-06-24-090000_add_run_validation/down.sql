@@ -0,0 +1,2 @@
+ALTER TABLE benchmarks DROP COLUMN validation_message;
+ALTER TABLE benchmarks DROP COLUMN validation;
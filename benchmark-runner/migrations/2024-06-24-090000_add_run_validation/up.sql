@@ -0,0 +1,2 @@
+ALTER TABLE benchmarks ADD COLUMN validation VARCHAR(32);
+ALTER TABLE benchmarks ADD COLUMN validation_message TEXT;
@@ -0,0 +1 @@
+ALTER TABLE gn_test DROP COLUMN seq;
@@ -0,0 +1,14 @@
+-- the drivers create gn_test on their first run, the runner reads it before that
+CREATE TABLE IF NOT EXISTS gn_test(
+    id INTEGER,
+    algo VARCHAR(256),
+    dataset VARCHAR(256),
+    type VARCHAR(256),
+    time INTEGER,
+    vertex INTEGER,
+    edge INTEGER,
+    nodes INTEGER
+);
+
+-- order in which the measurements were written, to number the repeats of a run
+ALTER TABLE gn_test ADD COLUMN IF NOT EXISTS seq BIGSERIAL;
@@ -1,4 +1,4 @@
-use clap::{ArgAction, Args, Parser, Subcommand};
+use clap::{ArgAction, Args, Parser, Subcommand, ValueEnum};
 
 #[derive(Debug, Parser)]
 #[command(name = "git")]
@@ -22,6 +22,20 @@ pub enum Commands {
     Setup(SetupArgs),
     /// Run benchmarks
     Benchmark(BenchmarkArgs),
+    /// Print the benchmark plan without running it
+    Plan,
+    /// Validate the configuration file
+    Validate,
+    /// Export benchmark results
+    Export(ExportArgs),
+    /// Print statistics of repeated runs
+    Summary(SummaryArgs),
+    /// Compare the results of two campaigns
+    Compare(CompareArgs),
+    /// Render charts & a summary from exported results
+    Report(ReportArgs),
+    /// Manage benchmark campaigns
+    Campaigns(CampaignsArgs),
     /// Teardown platform & kubernetes
     Destroy,
     /// List Resources
@@ -40,4 +54,102 @@ pub struct SetupArgs {
 }
 
 #[derive(Debug, Args)]
-pub struct BenchmarkArgs {}
+pub struct BenchmarkArgs {
+    /// Resume a previous benchmark, skipping runs already recorded in the journal
+    #[arg(long, short, action = ArgAction::SetTrue)]
+    pub resume: bool,
+
+    /// Campaign to group the runs under, overrides `benchmark.campaign`
+    #[arg(long)]
+    pub campaign: Option<String>,
+
+    /// Also write the summary of the runs to a Markdown or HTML (.html) report file
+    #[arg(long)]
+    pub report: Option<String>,
+
+    /// Remove the installed graph platforms when the benchmark is interrupted with Ctrl-C
+    #[arg(long, action = ArgAction::SetTrue)]
+    pub remove_on_interrupt: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct ReportArgs {
+    /// Results exported as csv or json
+    #[arg(long, short, default_value = "results/results.csv")]
+    pub input: String,
+
+    /// Output directory
+    #[arg(long, short, default_value = "results/report")]
+    pub output: String,
+
+    /// Only report the runs of this campaign
+    #[arg(long)]
+    pub campaign: Option<String>,
+}
+
+#[derive(Debug, Args)]
+pub struct CompareArgs {
+    /// Baseline campaign
+    pub baseline: String,
+
+    /// Campaign compared against the baseline
+    pub candidate: String,
+
+    /// Slowdown of the median runtime, in percent, above which a cell regressed
+    #[arg(long, default_value_t = 10.0)]
+    pub threshold: f64,
+
+    /// Significance level of the Mann-Whitney U test on the repeats of a cell
+    #[arg(long, default_value_t = 0.05)]
+    pub alpha: f64,
+}
+
+#[derive(Debug, Args)]
+pub struct CampaignsArgs {
+    #[command(subcommand)]
+    pub command: CampaignsCommands,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum CampaignsCommands {
+    /// List campaigns
+    Ls,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum ExportFormat {
+    Csv,
+    /// JSON lines, one run per line
+    Json,
+    Parquet,
+}
+
+#[derive(Debug, Args)]
+pub struct ExportArgs {
+    /// Output format
+    #[arg(long, value_enum, default_value_t = ExportFormat::Csv)]
+    pub format: ExportFormat,
+
+    /// Output file [default: results/results.<format>]
+    #[arg(long, short)]
+    pub output: Option<String>,
+
+    /// Only export these run ids
+    #[arg(long, value_delimiter = ',')]
+    pub runs: Option<Vec<i32>>,
+
+    /// Only export the runs of this campaign
+    #[arg(long)]
+    pub campaign: Option<String>,
+}
+
+#[derive(Debug, Args)]
+pub struct SummaryArgs {
+    /// Only summarize the runs of this campaign
+    #[arg(long)]
+    pub campaign: Option<String>,
+
+    /// Also write the summary to a Markdown or HTML (.html) report file
+    #[arg(long)]
+    pub report: Option<String>,
+}
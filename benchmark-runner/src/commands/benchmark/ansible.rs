@@ -1,52 +1,37 @@
 use std::collections::HashMap;
 
 use anyhow::Result;
-use common::{command::command_print, config::Config, provider::PlatformInfo};
+use common::{
+    command::{command_print, retry},
+    config::{Config, RetryStep},
+    provider::PlatformInfo,
+};
 use tokio::fs;
 
 use crate::commands::setup::Item;
 
 pub async fn join_all_nodes(connect_args: &PlatformInfo, verbose: bool) -> Result<()> {
-    command_print(
-        "ansible-playbook",
-        &[
-            "k3s-agent.yaml",
-            "--private-key",
-            &connect_args.ssh_key,
-            "-i",
-            "inventory/worker-hosts.yaml",
-        ],
-        verbose,
-        [
-            &format!("Rejoining all nodes to cluster"),
-            &format!("Could not join all nodes to cluster"),
-            &format!("All nodes have joined the cluster"),
-        ],
-        "k3s",
-        HashMap::from([("ANSIBLE_HOST_KEY_CHECKING", "False")]),
-    )
-    .await
-}
-
-pub async fn clear_dirs(connect_args: &PlatformInfo, verbose: bool) -> Result<()> {
-    command_print(
-        "ansible-playbook",
-        &[
-            "clear-visualizations.yaml",
-            "--private-key",
-            &connect_args.ssh_key,
-            "-i",
-            "inventory/master-hosts.yaml",
-        ],
-        verbose,
-        [
-            &format!("Clearing visualization folder"),
-            &format!("Could not clear visualization folder"),
-            &format!("Cleared visualization folder"),
-        ],
-        "k3s",
-        HashMap::from([("ANSIBLE_HOST_KEY_CHECKING", "False")]),
-    )
+    let args = [
+        "k3s-agent.yaml",
+        "--private-key",
+        &connect_args.ssh_key,
+        "-i",
+        "inventory/worker-hosts.yaml",
+    ];
+    retry(RetryStep::Ansible, "join all nodes", || {
+        command_print(
+            "ansible-playbook",
+            &args,
+            verbose,
+            [
+                "Rejoining all nodes to cluster",
+                "Could not join all nodes to cluster",
+                "All nodes have joined the cluster",
+            ],
+            "k3s",
+            HashMap::from([("ANSIBLE_HOST_KEY_CHECKING", "False")]),
+        )
+    })
     .await
 }
 
@@ -56,34 +41,45 @@ pub async fn copy_datasets(
     username: &str,
     verbose: bool,
 ) -> Result<()> {
+    let ssh = format!(
+        "ssh -i {} -o StrictHostKeyChecking=no",
+        connect_args.ssh_key
+    );
+    let destination = format!(
+        "{username}@{}:/cluster-data/bench-storage/",
+        connect_args.master_ip
+    );
     for dataset in datasets {
-        command_print(
-            "rsync",
-            &[
-                "--blocking-io",
-                "-av",
-                "--progress",
-                "--stats",
-                "-rsh",
-                "-e",
-                &format!(
-                    "ssh -i {} -o StrictHostKeyChecking=no",
-                    connect_args.ssh_key
-                ),
-                &format!("../datasets/{dataset}/"),
-                &format!(
-                    "{username}@{}:/cluster-data/bench-storage/",
-                    connect_args.master_ip
-                ),
-            ],
-            verbose,
-            [
-                &format!("Copying dataset {dataset}"),
-                &format!("Could not copy dataset {dataset}"),
-                &format!("Copied dataset {dataset}"),
-            ],
-            "k3s",
-            HashMap::<&str, &str>::from([("KUBECONFIG", "kube-config")]),
+        let source = format!("../datasets/{dataset}/");
+        let msgs = [
+            format!("Copying dataset {dataset}"),
+            format!("Could not copy dataset {dataset}"),
+            format!("Copied dataset {dataset}"),
+        ];
+        let args = [
+            "--blocking-io",
+            "-av",
+            "--progress",
+            "--stats",
+            "-rsh",
+            "-e",
+            &ssh,
+            &source,
+            &destination,
+        ];
+        retry(
+            RetryStep::Datasets,
+            &format!("copy dataset {dataset}"),
+            || {
+                command_print(
+                    "rsync",
+                    &args,
+                    verbose,
+                    msgs.each_ref().map(|x| x.as_str()),
+                    "k3s",
+                    HashMap::<&str, &str>::from([("KUBECONFIG", "kube-config")]),
+                )
+            },
         )
         .await?;
     }
@@ -91,41 +87,16 @@ pub async fn copy_datasets(
     Ok(())
 }
 
-pub async fn copy_generated_graphs(verbose: bool, connect_args: &PlatformInfo) -> Result<()> {
-    command_print(
-        "ansible-playbook",
-        &[
-            "copy-graphs.yaml",
-            "--private-key",
-            &connect_args.ssh_key,
-            "-i",
-            "inventory/master-hosts.yaml",
-        ],
-        verbose,
-        [
-            &format!("Copying generated graphs"),
-            &format!("Could not copy generated graphs"),
-            &format!("Copied generated graphs"),
-        ],
-        "k3s",
-        HashMap::from([("ANSIBLE_HOST_KEY_CHECKING", "False")]),
-    )
-    .await
-}
-
 pub async fn remove_graph_platform(
     driver: &str,
     connect_args: &PlatformInfo,
     extra_vars: Vec<String>,
     verbose: bool,
 ) -> Result<()> {
-    let mut args = vec![
-        "remove.yaml",
-        "--private-key",
-        &connect_args.ssh_key,
-        "-i",
-        "../../k3s/inventory/master-hosts.yaml",
-    ];
+    let mut args = vec!["remove.yaml", "-i", "../../k3s/inventory/master-hosts.yaml"];
+    if !connect_args.ssh_key.is_empty() {
+        args.extend(["--private-key", &connect_args.ssh_key]);
+    }
 
     let extra_vars_str = extra_vars.join(" ");
     if extra_vars.len() > 0 {
@@ -133,18 +104,22 @@ pub async fn remove_graph_platform(
         args.push(&extra_vars_str);
     }
 
-    command_print(
-        "ansible-playbook",
-        &args,
-        verbose,
-        [
-            &format!("Removing driver {driver}"),
-            &format!("Could not remove {driver}"),
-            &format!("Removed {driver}"),
-        ],
-        &format!("drivers/{}", driver),
-        HashMap::from([("ANSIBLE_HOST_KEY_CHECKING", "False")]),
-    )
+    let msgs = [
+        format!("Removing driver {driver}"),
+        format!("Could not remove {driver}"),
+        format!("Removed {driver}"),
+    ];
+    let dir = format!("drivers/{}", driver);
+    retry(RetryStep::Ansible, &format!("remove {driver}"), || {
+        command_print(
+            "ansible-playbook",
+            &args,
+            verbose,
+            msgs.each_ref().map(|x| x.as_str()),
+            &dir,
+            HashMap::from([("ANSIBLE_HOST_KEY_CHECKING", "False")]),
+        )
+    })
     .await
 }
 
@@ -158,14 +133,7 @@ pub async fn remove_node(
         "workers",
         Item {
             hosts: HashMap::from([(node.to_string(), ())]),
-            vars: HashMap::from([(
-                "ansible_user",
-                config
-                    .setup
-                    .host_username
-                    .clone()
-                    .unwrap_or("root".to_owned()),
-            )]),
+            vars: HashMap::from([("ansible_user", connect_args.username(&config.setup))]),
         },
     )]);
     fs::write(
@@ -173,23 +141,26 @@ pub async fn remove_node(
         serde_yaml::to_string(&c)?,
     )
     .await?;
-    command_print(
-        "ansible-playbook",
-        &[
-            "uninstall-worker.yaml",
-            "--private-key",
-            &connect_args.ssh_key,
-            "-i",
-            "inventory/remove-node.yaml",
-        ],
-        verbose,
-        [
-            &format!("Removing node from the cluster"),
-            &format!("Could not node from the cluster"),
-            &format!("Removed node from the cluster"),
-        ],
-        "k3s",
-        HashMap::from([("ANSIBLE_HOST_KEY_CHECKING", "False")]),
-    )
+    let args = [
+        "uninstall-worker.yaml",
+        "--private-key",
+        &connect_args.ssh_key,
+        "-i",
+        "inventory/remove-node.yaml",
+    ];
+    retry(RetryStep::Ansible, &format!("remove node {node}"), || {
+        command_print(
+            "ansible-playbook",
+            &args,
+            verbose,
+            [
+                "Removing node from the cluster",
+                "Could not node from the cluster",
+                "Removed node from the cluster",
+            ],
+            "k3s",
+            HashMap::from([("ANSIBLE_HOST_KEY_CHECKING", "False")]),
+        )
+    })
     .await
 }
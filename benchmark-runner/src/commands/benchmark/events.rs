@@ -0,0 +1,121 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+use anyhow::Result;
+use common::exit;
+use futures_util::{stream::SplitStream, TryStreamExt};
+use tokio::{
+    net::TcpStream,
+    spawn,
+    sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender},
+};
+use tokio_tungstenite::{MaybeTlsStream, WebSocketStream};
+use tracing::warn;
+
+use super::types::BenchStartEvent;
+
+type Routes = Arc<Mutex<Subscribers>>;
+
+#[derive(Default)]
+struct Subscribers {
+    senders: HashMap<i32, UnboundedSender<bool>>,
+    /// The notifier stream ended, no more events will come
+    closed: bool,
+}
+
+/// Routes the start & stop events of the notifier to the benchmark of the driver the run belongs
+/// to, as drivers benchmarked in parallel share the notifier
+#[derive(Clone, Default)]
+pub struct EventRouter {
+    routes: Routes,
+}
+
+impl EventRouter {
+    /// Forward the events of the notifier stream until it is closed, then end the subscriptions so
+    /// the benchmarks waiting for an event fail
+    pub fn spawn(mut stream: SplitStream<WebSocketStream<MaybeTlsStream<TcpStream>>>) -> Self {
+        let router = Self::default();
+        let routes = router.routes.clone();
+        spawn(async move {
+            loop {
+                let msg = match stream.try_next().await {
+                    Ok(Some(msg)) => msg,
+                    Ok(None) => {
+                        warn!("notifier stream closed");
+                        break;
+                    }
+                    Err(err) => {
+                        warn!("notifier stream failed: {err}");
+                        break;
+                    }
+                };
+                let Ok(text) = msg.into_text() else { continue };
+                match serde_json::from_str::<BenchStartEvent>(&text) {
+                    Ok(event) => route(&routes, event),
+                    Err(err) => warn!("invalid notifier event {text}: {err}"),
+                }
+            }
+            let mut routes = routes.lock().unwrap();
+            routes.closed = true;
+            routes.senders.clear();
+        });
+        router
+    }
+
+    /// Receive the events of the runs, whether each of them is starting or stopping. The
+    /// subscription ends right away if the notifier stream is closed
+    pub fn subscribe(&self, run_ids: &[i32]) -> UnboundedReceiver<bool> {
+        let (tx, rx) = unbounded_channel();
+        let mut routes = self.routes.lock().unwrap();
+        if !routes.closed {
+            for id in run_ids {
+                routes.senders.insert(*id, tx.clone());
+            }
+        }
+        rx
+    }
+
+    pub fn unsubscribe(&self, run_ids: &[i32]) {
+        let mut routes = self.routes.lock().unwrap();
+        for id in run_ids {
+            routes.senders.remove(id);
+        }
+    }
+}
+
+/// Send an event to the subscriber of its run. Drivers that do not report the run id can only be
+/// told apart while a single benchmark is running
+fn route(routes: &Routes, event: BenchStartEvent) {
+    let routes = routes.lock().unwrap();
+    let tx = match event.id {
+        Some(id) => routes.senders.get(&id),
+        None => {
+            let mut subscribers = routes.senders.values();
+            let first = subscribers.next();
+            first.filter(|x| subscribers.all(|y| x.same_channel(y)))
+        }
+    };
+    match tx {
+        Some(tx) => _ = tx.send(event.status),
+        None => warn!("no benchmark is waiting for notifier event {event:?}"),
+    }
+}
+
+/// Wait for the next event of a subscription, failing if it is not the expected one
+pub async fn next_event(events: &mut UnboundedReceiver<bool>, starting: bool) -> Result<()> {
+    match events.recv().await {
+        Some(status) if status == starting => Ok(()),
+        Some(_) if starting => exit!(
+            "",
+            "Expected bench starting message, got bench ending message"
+        ),
+        Some(_) => exit!(
+            "",
+            "Expected bench stopping message, got bench starting message"
+        ),
+        None if starting => exit!("", "Received incorrect benchmark starting signal"),
+        None => exit!("", "Received incorrect benchmark stopping signal"),
+    }
+}
@@ -0,0 +1,231 @@
+use std::path::PathBuf;
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use tokio::{fs, io::AsyncWriteExt};
+use tracing::info;
+
+use super::types::Run;
+
+const JOURNAL_FILE: &str = "results/journal.jsonl";
+
+/// A single completed algorithm run, appended to the journal as soon as the driver reports it finished
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JournalEntry {
+    pub nodes: usize,
+    pub driver: String,
+    pub dataset: String,
+    pub repeat: usize,
+    pub algorithm: String,
+    pub run_id: i32,
+    #[serde(default)]
+    pub campaign_id: Option<i32>,
+    /// Hash of the setup the run was measured on, runs of another setup are benchmarked again
+    #[serde(default)]
+    pub config_hash: Option<String>,
+}
+
+pub struct Journal {
+    path: PathBuf,
+    entries: Vec<JournalEntry>,
+}
+
+impl Journal {
+    /// Open the journal, keeping previous entries only when resuming
+    pub async fn open(resume: bool) -> Result<Self> {
+        let path = PathBuf::from(JOURNAL_FILE);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).await?;
+        }
+
+        let mut entries = Vec::new();
+        if resume {
+            entries = Self::read().await?;
+            info!("loaded {} journal entries", entries.len());
+        } else {
+            fs::write(&path, "").await?;
+        }
+
+        Ok(Self { path, entries })
+    }
+
+    /// Read the entries of the journal without modifying it
+    pub async fn read() -> Result<Vec<JournalEntry>> {
+        let path = PathBuf::from(JOURNAL_FILE);
+        let mut entries = Vec::new();
+        if !path.exists() {
+            return Ok(entries);
+        }
+
+        for line in fs::read_to_string(&path).await?.lines() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            entries.push(serde_json::from_str::<JournalEntry>(line)?);
+        }
+        Ok(entries)
+    }
+
+    pub async fn record(&mut self, entry: JournalEntry) -> Result<()> {
+        let mut f = fs::OpenOptions::new()
+            .append(true)
+            .create(true)
+            .open(&self.path)
+            .await?;
+        f.write_all(format!("{}\n", serde_json::to_string(&entry)?).as_bytes())
+            .await?;
+        f.flush().await?;
+        self.entries.push(entry);
+        Ok(())
+    }
+
+    /// Forget the entries of other campaigns, their runs are not part of the resumed one
+    pub fn keep_campaign(&mut self, campaign_id: i32) {
+        let before = self.entries.len();
+        self.entries.retain(|x| x.campaign_id == Some(campaign_id));
+        if self.entries.len() < before {
+            info!(
+                "ignoring {} journal entries of other campaigns",
+                before - self.entries.len()
+            );
+        }
+    }
+
+    pub fn is_done(
+        &self,
+        config_hash: &str,
+        nodes: usize,
+        driver: &str,
+        dataset: &str,
+        repeat: usize,
+        algorithm: &str,
+    ) -> bool {
+        self.entries.iter().any(|x| {
+            x.config_hash.as_deref() == Some(config_hash)
+                && x.nodes == nodes
+                && x.driver == driver
+                && x.dataset == dataset
+                && x.repeat == repeat
+                && x.algorithm == algorithm
+        })
+    }
+
+    /// Whether every repeat of every algorithm has been recorded for a dataset
+    pub fn is_complete(
+        &self,
+        config_hash: &str,
+        nodes: usize,
+        driver: &str,
+        dataset: &str,
+        repeats: usize,
+        algorithms: &[String],
+    ) -> bool {
+        (0..repeats).all(|repeat| {
+            algorithms
+                .iter()
+                .all(|algo| self.is_done(config_hash, nodes, driver, dataset, repeat, algo))
+        })
+    }
+
+    /// Campaign the journaled runs belong to
+    pub fn campaign_id(&self) -> Option<i32> {
+        self.entries.iter().rev().find_map(|x| x.campaign_id)
+    }
+
+    /// Runs of a dataset that were completed before resuming
+    pub fn completed_runs(
+        &self,
+        config_hash: &str,
+        nodes: usize,
+        driver: &str,
+        dataset: &str,
+    ) -> Vec<Run> {
+        self.entries
+            .iter()
+            .filter(|x| {
+                x.config_hash.as_deref() == Some(config_hash)
+                    && x.nodes == nodes
+                    && x.driver == driver
+                    && x.dataset == dataset
+            })
+            .map(|x| Run {
+                driver: x.driver.clone(),
+                dataset: x.dataset.clone(),
+                algorithm: x.algorithm.clone(),
+                nodes: x.nodes,
+                repeat: x.repeat,
+                run_id: x.run_id,
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(config_hash: &str, repeat: usize, algorithm: &str) -> JournalEntry {
+        JournalEntry {
+            nodes: 2,
+            driver: "native".to_owned(),
+            dataset: "tiny".to_owned(),
+            repeat,
+            algorithm: algorithm.to_owned(),
+            run_id: repeat as i32,
+            campaign_id: Some(1),
+            config_hash: Some(config_hash.to_owned()),
+        }
+    }
+
+    fn journal(entries: Vec<JournalEntry>) -> Journal {
+        Journal {
+            path: PathBuf::from(JOURNAL_FILE),
+            entries,
+        }
+    }
+
+    fn algorithms() -> Vec<String> {
+        vec!["bfs".to_owned(), "pr".to_owned()]
+    }
+
+    #[test]
+    fn partial_repeats_are_not_complete() {
+        let journal = journal(vec![
+            entry("a", 0, "bfs"),
+            entry("a", 0, "pr"),
+            entry("a", 1, "bfs"),
+        ]);
+        assert!(journal.is_done("a", 2, "native", "tiny", 1, "bfs"));
+        assert!(!journal.is_done("a", 2, "native", "tiny", 1, "pr"));
+        assert!(!journal.is_done("a", 4, "native", "tiny", 0, "bfs"));
+        assert!(journal.is_complete("a", 2, "native", "tiny", 1, &algorithms()));
+        assert!(!journal.is_complete("a", 2, "native", "tiny", 2, &algorithms()));
+        assert_eq!(journal.completed_runs("a", 2, "native", "tiny").len(), 3);
+        assert!(journal.completed_runs("a", 2, "neo4j", "tiny").is_empty());
+    }
+
+    #[test]
+    fn runs_of_another_config_are_not_done() {
+        let mut old = entry("a", 0, "pr");
+        old.config_hash = None;
+        let journal = journal(vec![entry("a", 0, "bfs"), old]);
+        assert!(journal.is_done("a", 2, "native", "tiny", 0, "bfs"));
+        assert!(!journal.is_done("b", 2, "native", "tiny", 0, "bfs"));
+        assert!(!journal.is_done("a", 2, "native", "tiny", 0, "pr"));
+        assert!(!journal.is_complete("b", 2, "native", "tiny", 1, &algorithms()));
+        assert_eq!(journal.completed_runs("a", 2, "native", "tiny").len(), 1);
+        assert!(journal.completed_runs("b", 2, "native", "tiny").is_empty());
+    }
+
+    #[test]
+    fn runs_of_other_campaigns_are_forgotten() {
+        let mut other = entry("a", 0, "pr");
+        other.campaign_id = Some(2);
+        let mut journal = journal(vec![entry("a", 0, "bfs"), other]);
+        assert_eq!(journal.campaign_id(), Some(2));
+        journal.keep_campaign(1);
+        assert!(journal.is_done("a", 2, "native", "tiny", 0, "bfs"));
+        assert!(!journal.is_done("a", 2, "native", "tiny", 0, "pr"));
+        assert_eq!(journal.campaign_id(), Some(1));
+    }
+}
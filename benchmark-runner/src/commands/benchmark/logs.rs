@@ -0,0 +1,157 @@
+use std::{
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use futures::{AsyncBufReadExt, TryStreamExt};
+use k8s_openapi::api::core::v1::Pod;
+use kube::{
+    api::{ListParams, LogParams},
+    Api, ResourceExt,
+};
+use tokio::{
+    fs,
+    io::AsyncWriteExt,
+    spawn,
+    task::JoinHandle,
+    time::{sleep, timeout},
+};
+use tracing::{info, warn};
+
+const RESULTS_DIR: &str = "results";
+const BENCH_LOG: &str = "bench.log";
+/// Time the benchmark pod gets to finish its log once its job is over
+pub const LOG_GRACE: Duration = Duration::from_secs(60);
+
+/// Directory the logs & config of a run are archived to
+pub fn run_dir(campaign: &str, run_id: i32) -> PathBuf {
+    Path::new(RESULTS_DIR)
+        .join(campaign)
+        .join(run_id.to_string())
+}
+
+/// Archive of the logs & config of a benchmark job, copied to the directory of every run in it as
+/// a job benchmarks several algorithms
+pub struct JobArchive {
+    dirs: Vec<PathBuf>,
+    started: DateTime<Utc>,
+    follower: Option<JoinHandle<()>>,
+}
+
+impl JobArchive {
+    /// Create the directories of the runs, with the config the benchmark job is started with
+    pub async fn open(campaign: &str, run_ids: &[i32], config: &str) -> Result<Self> {
+        let dirs = run_ids
+            .iter()
+            .map(|x| run_dir(campaign, *x))
+            .collect::<Vec<_>>();
+        for dir in &dirs {
+            fs::create_dir_all(dir).await?;
+            fs::write(dir.join("config.yaml"), config).await?;
+        }
+        Ok(Self {
+            dirs,
+            started: Utc::now(),
+            follower: None,
+        })
+    }
+
+    /// Stream the log of the benchmark pod matching `selector` as it is written, as the pod is
+    /// gone once the ttl of its job expires
+    pub fn follow(&mut self, pods: Api<Pod>, selector: String) {
+        let dirs = self.dirs.clone();
+        self.follower = Some(spawn(async move {
+            if let Err(err) = follow_log(&pods, &selector, &dirs).await {
+                warn!("could not archive the log of {selector}: {err:#}");
+            }
+        }));
+    }
+
+    /// Wait up to `grace` for the log of the benchmark pod to end, and archive the logs the
+    /// `platform_pods` wrote since the job started
+    pub async fn finish(mut self, pods: &Api<Pod>, platform_pods: &[String], grace: Duration) {
+        if let Some(mut follower) = self.follower.take() {
+            if timeout(grace, &mut follower).await.is_err() {
+                warn!("benchmark log did not end, archiving it as is");
+                follower.abort();
+            }
+        }
+
+        for pod in platform_pods {
+            if let Err(err) = self.archive_pod(pods, pod).await {
+                warn!("could not archive the logs of {pod}: {err:#}");
+            }
+        }
+        info!("archived logs to {:?}", self.dirs);
+    }
+
+    async fn archive_pod(&self, pods: &Api<Pod>, name: &str) -> Result<()> {
+        let pod = pods.get(name).await?;
+        let containers = pod
+            .spec
+            .map(|x| x.containers.into_iter().map(|x| x.name).collect())
+            .unwrap_or_else(Vec::new);
+        for container in &containers {
+            let log = pods
+                .logs(
+                    name,
+                    &LogParams {
+                        container: Some(container.clone()),
+                        since_time: Some(self.started),
+                        ..LogParams::default()
+                    },
+                )
+                .await?;
+            let file = match containers.len() {
+                1 => format!("{name}.log"),
+                _ => format!("{name}.{container}.log"),
+            };
+            for dir in &self.dirs {
+                fs::write(dir.join(&file), &log).await?;
+            }
+        }
+        Ok(())
+    }
+}
+
+async fn follow_log(pods: &Api<Pod>, selector: &str, dirs: &[PathBuf]) -> Result<()> {
+    let name = loop {
+        let list = pods.list(&ListParams::default().labels(selector)).await?;
+        let started = list.items.into_iter().find(|x| {
+            x.status
+                .as_ref()
+                .and_then(|x| x.phase.as_deref())
+                .is_some_and(|x| x != "Pending")
+        });
+        if let Some(pod) = started {
+            break pod.name_any();
+        }
+        sleep(Duration::from_secs(1)).await;
+    };
+    info!("following the log of {name}");
+
+    let log = dirs[0].join(BENCH_LOG);
+    let mut file = fs::File::create(&log).await?;
+    let mut lines = pods
+        .log_stream(
+            &name,
+            &LogParams {
+                follow: true,
+                ..LogParams::default()
+            },
+        )
+        .await?
+        .lines();
+    while let Some(line) = lines.try_next().await? {
+        file.write_all(line.as_bytes()).await?;
+        file.write_all(b"\n").await?;
+    }
+    file.flush().await?;
+
+    for dir in &dirs[1..] {
+        fs::copy(&log, dir.join(BENCH_LOG)).await?;
+    }
+    Ok(())
+}
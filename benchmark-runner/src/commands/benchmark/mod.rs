@@ -1,54 +1,82 @@
 use std::{
     collections::{BTreeMap, HashMap},
-    env,
+    fmt::Display,
+    future::Future,
     net::IpAddr,
-    time::Instant,
+    path::Path,
+    time::{Duration, Instant},
 };
 
 use anyhow::Result;
 use common::{
-    command::{command_print, finish_progress, progress},
-    config::{parse_config, Config},
+    command::{command_print, fail_progress, finish_progress, progress, retry, set_retry_policy},
+    config::{Config, RetryStep, ALGORITHMS},
+    driver_config::{NodePool, POOL_LABEL},
     exit,
     provider::PlatformInfo,
 };
-use diesel::{Connection, ExpressionMethods, SelectableHelper};
+use console::style;
+use diesel::{dsl::now, Connection, ExpressionMethods, QueryDsl, SelectableHelper};
 use diesel_async::{
     async_connection_wrapper::AsyncConnectionWrapper, AsyncConnection, AsyncPgConnection,
     RunQueryDsl,
 };
 use diesel_migrations::{embed_migrations, EmbeddedMigrations, MigrationHarness};
-use futures_util::{StreamExt, TryStreamExt};
+use futures_util::{future::join_all, SinkExt, StreamExt, TryStreamExt};
 use k8s_openapi::api::{
     batch::v1::{Job, JobSpec},
     core::v1::{
-        ConfigMap, ConfigMapVolumeSource, Container, NFSVolumeSource, Node, Pod, PodSpec,
+        ConfigMap, ConfigMapVolumeSource, Container, Event, NFSVolumeSource, Node, Pod, PodSpec,
         PodTemplateSpec, Service, Volume, VolumeMount,
     },
 };
 use kube::{
-    api::{DeleteParams, ListParams, PostParams, WatchParams},
+    api::{DeleteParams, ListParams, Patch, PatchParams, PostParams, WatchParams},
     core::{ObjectMeta, WatchEvent},
     Api, Client,
 };
-use tokio::{fs, net::TcpStream, spawn};
-use tokio_tungstenite::{connect_async, MaybeTlsStream, WebSocketStream};
-use tracing::info;
+use sha2::{Digest, Sha256};
+use tokio::{
+    fs, select,
+    signal::ctrl_c,
+    spawn,
+    sync::Mutex,
+    time::{sleep, timeout},
+};
+use tokio_tungstenite::connect_async;
+use tracing::{info, warn};
 
 use crate::{
-    args::Cli,
+    args::{BenchmarkArgs, Cli},
+    commands::{
+        campaigns::{finish_campaign, open_campaign},
+        cluster::{copy_datasets_to_pod, forward_services, running_pod, use_kube_config, Cluster},
+        summary::{print_scaling, print_summary, write_report},
+        validate::load_config,
+    },
     metrics_utils::{start_recording, stop_recording},
-    model::Benchmark,
+    model::{Benchmark, NewBenchmark, NewRunEvent, RunStatus, Validation},
+    report::render_charts,
+    results::fetch_run_records,
+    stats::{scaling, summarize},
 };
 
-use self::{ansible::*, helpers::*, types::*};
+use self::{
+    ansible::*, events::*, helpers::*, journal::*, logs::*, monitor::*, types::*, validation::*,
+};
 
 mod ansible;
+mod events;
 mod helpers;
-mod types;
+pub(crate) mod journal;
+mod logs;
+mod monitor;
+pub(crate) mod types;
+mod validation;
 
 pub const MIGRATIONS: EmbeddedMigrations = embed_migrations!();
-const ALGORITHMS: &[&str] = &["bfs", "pr", "wcc", "cdlp", "lcc", "sssp"];
+/// Charts of every benchmark are rendered here once it finishes
+const CHARTS_DIR: &str = "results/graphs";
 const POSTGRES_CONFIG: PostgresConfig = PostgresConfig {
     host: "postgres",
     db: "postgres",
@@ -57,46 +85,37 @@ const POSTGRES_CONFIG: PostgresConfig = PostgresConfig {
     ps: "graph_benchmarks",
 };
 
-pub async fn run_benchmark(cli: &Cli) -> Result<()> {
-    let mut config = parse_config(&cli.file)?;
+pub async fn run_benchmark(args: &BenchmarkArgs, cli: &Cli) -> Result<()> {
+    let mut config = load_config(&cli.file)?;
+    set_retry_policy(config.retry.clone());
+    let datasets = load_datasets(&config).await?;
 
-    let connect_args = 'p: {
-        for p in base_provider::PROVIDERS {
-            if p.name() == config.setup.provider {
-                break 'p p.platform_info(&config.setup, cli.verbose).await?;
-            }
-        }
-        exit!("", "Unknown platform {}", config.setup.platform)
-    };
+    let connect_args = platform_info(&config, cli.verbose).await?;
+    let cluster = Cluster::new(&config, &connect_args.master_ip);
 
     setup_db(connect_args.master_ip.clone())?;
-    let mut connection = AsyncPgConnection::establish(&format!(
-        "postgres://{}:{}@{}:30002/{}",
-        POSTGRES_CONFIG.user, POSTGRES_CONFIG.ps, connect_args.master_ip, POSTGRES_CONFIG.db
-    ))
-    .await?;
+    let mut connection = connect_db(&connect_args.master_ip).await?;
 
-    env::set_var("KUBECONFIG", "k3s/kube-config");
     config.setup.node_configs.sort_by(|a, b| b.cmp(a));
 
-    start_notifier(connect_args.master_ip.to_string()).await?;
-    copy_datasets(
-        &config.benchmark.datasets,
-        &connect_args,
-        config
-            .setup
-            .host_username
-            .as_ref()
-            .unwrap_or(&"root".to_owned()),
-        cli.verbose,
-    )
-    .await?;
-    clear_dirs(&connect_args, cli.verbose).await?;
+    start_notifier(&cluster).await?;
+    if cluster.existing {
+        copy_datasets_to_pod(&cluster, &config.benchmark.datasets, cli.verbose).await?;
+    } else {
+        copy_datasets(
+            &config.benchmark.datasets,
+            &connect_args,
+            &connect_args.username(&config.setup),
+            cli.verbose,
+        )
+        .await?;
+    }
 
     let client = Client::try_default().await?;
     let nodes: Api<Node> = Api::all(client);
     let nodes = nodes.list(&ListParams::default()).await?;
-    if nodes.items.len() != config.setup.node_configs[0]
+    if !cluster.existing
+        && nodes.items.len() != config.cluster_size(config.setup.node_configs[0])
         && config
             .benchmark
             .debug
@@ -107,9 +126,9 @@ pub async fn run_benchmark(cli: &Cli) -> Result<()> {
     {
         join_all_nodes(&connect_args, cli.verbose).await?;
     }
-    start_metrics(&connect_args.master_ip.to_string()).await?;
+    start_metrics(&cluster).await?;
 
-    let svc: Api<Service> = Api::default_namespaced(Client::try_default().await?);
+    let svc: Api<Service> = cluster.api(Client::try_default().await?);
     let nfs_ip = svc
         .get("nfs-service")
         .await?
@@ -119,215 +138,621 @@ pub async fn run_benchmark(cli: &Cli) -> Result<()> {
         .unwrap();
 
     let mut runs: Vec<Run> = Vec::new();
+    let mut journal = Journal::open(args.resume).await?;
+    let campaign = open_campaign(
+        &mut connection,
+        args.campaign.clone().or(config.benchmark.campaign.clone()),
+        journal.campaign_id(),
+        &config,
+        &cli.file,
+    )
+    .await?;
+    println!("Benchmarking campaign {}", campaign.name);
+    journal.keep_campaign(campaign.id);
+
+    let (ws_stream, _) = connect_async(format!("ws://{}:30003/ws", connect_args.master_ip)).await?;
+    let (mut ws_sink, ws_stream) = ws_stream.split();
+    let journal = Mutex::new(journal);
+    let ctx = BenchContext {
+        config: &config,
+        cli,
+        connect_args: &connect_args,
+        cluster: &cluster,
+        datasets: &datasets,
+        campaign_id: campaign.id,
+        campaign_name: &campaign.name,
+        nfs_ip,
+        journal: &journal,
+        events: EventRouter::spawn(ws_stream),
+        leftovers: Mutex::default(),
+    };
+
+    let bench = async {
+        for n_nodes in config.setup.node_configs.clone() {
+            let pools =
+                new_cluster_node_count(n_nodes, &cluster, &connect_args, &config, cli.verbose)
+                    .await?;
 
-    let (mut ws_stream, _) =
-        connect_async(format!("ws://{}:30003/ws", connect_args.master_ip)).await?;
+            let mut drivers = Vec::new();
+            for driver in &config.benchmark.drivers {
+                let journal = journal.lock().await;
+                let hash = config_hash(&config, driver, n_nodes)?;
+                if datasets.iter().all(|d| {
+                    journal.is_complete(
+                        &hash,
+                        n_nodes,
+                        driver,
+                        &d.name,
+                        config.benchmark.repeat,
+                        &driver_algorithms(driver, &d.algorithms),
+                    )
+                }) {
+                    for d in &datasets {
+                        runs.extend(journal.completed_runs(&hash, n_nodes, driver, &d.name));
+                    }
+                    println!("Skipping {driver} on {n_nodes} nodes, already benchmarked");
+                    continue;
+                }
+                drivers.push(driver);
+            }
+
+            if config.parallel_drivers() {
+                let benches = drivers.iter().map(|driver| {
+                    let pool = pools.iter().find(|x| x.name == **driver);
+                    bench_driver(&ctx, driver, n_nodes, pool)
+                });
+                for driver_runs in join_all(benches).await {
+                    runs.extend(driver_runs?);
+                }
+            } else {
+                for driver in drivers {
+                    runs.extend(bench_driver(&ctx, driver, n_nodes, None).await?);
+                }
+            }
+        }
+        Ok::<_, anyhow::Error>(())
+    };
+    let interrupted = select! {
+        res = bench => {
+            res?;
+            false
+        }
+        _ = ctrl_c() => true,
+    };
+    if interrupted {
+        println!("Interrupted, cleaning up the cluster. Press Ctrl-C again to exit right away");
+        select! {
+            res = clean_up(&ctx, args.remove_on_interrupt) => res?,
+            _ = ctrl_c() => exit!("", "Cleanup interrupted, benchmark resources are left on the cluster"),
+        }
+        exit!("", "Benchmark interrupted, resume it with --resume");
+    }
 
-    for n_nodes in config.setup.node_configs.clone() {
-        new_cluster_node_count(n_nodes, &connect_args, &config, cli.verbose).await?;
-        for driver in &config.benchmark.drivers {
-            let driver_config = match base_driver::get_driver_config(driver) {
-                Some(d) => d,
-                None => exit!("", "Could not find driver {}", driver),
+    ws_sink.close().await?;
+    if config.parallel_drivers() {
+        release_node_pools().await?;
+    }
+
+    stop_pod_service(&cluster, "metrics").await?;
+    stop_pod_service(&cluster, "notifier").await?;
+    finish_campaign(&mut connection, campaign.id).await?;
+
+    let records = fetch_run_records(
+        &mut connection,
+        Some(runs.iter().map(|x| x.run_id).collect()),
+        None,
+    )
+    .await?;
+    let summaries = summarize(&records);
+    let scaling = scaling(&records);
+    print_summary(&summaries);
+    print_scaling(&scaling);
+
+    let pb = progress("Rendering charts");
+    let start = Instant::now();
+    let charts = render_charts(&records, Path::new(CHARTS_DIR))?;
+    finish_progress(
+        &format!("Rendered {} charts", charts.len()),
+        CHARTS_DIR,
+        start.elapsed(),
+        Some(pb),
+    );
+    if let Some(report) = &args.report {
+        write_report(&summaries, &scaling, Some(&campaign.name), report, &charts)?;
+        println!("Wrote report to {report}");
+    }
+
+    Ok(())
+}
+
+/// What the benchmarks of the drivers share, when they run one after the other or in parallel
+struct BenchContext<'a> {
+    config: &'a Config,
+    cli: &'a Cli,
+    connect_args: &'a PlatformInfo,
+    cluster: &'a Cluster,
+    datasets: &'a [BenchDataset],
+    campaign_id: i32,
+    campaign_name: &'a str,
+    nfs_ip: String,
+    journal: &'a Mutex<Journal>,
+    events: EventRouter,
+    leftovers: Mutex<Leftovers>,
+}
+
+/// What the benchmarks in progress leave on the cluster, cleaned up when they are interrupted
+#[derive(Default)]
+struct Leftovers {
+    /// Installed drivers, with the extra vars of their playbooks
+    installed: HashMap<String, Vec<String>>,
+    /// Runs recording metrics, with the pods they record
+    recording: HashMap<i32, Vec<String>>,
+}
+
+/// Clean up after the benchmarks interrupted by Ctrl-C: stop recording metrics, fail the runs in
+/// progress, delete the benchmark jobs & services, and remove the installed drivers if `remove`
+async fn clean_up(ctx: &BenchContext<'_>, remove: bool) -> Result<()> {
+    let leftovers = std::mem::take(&mut *ctx.leftovers.lock().await);
+    let metrics_ip = format!("http://{}:30001", ctx.connect_args.master_ip);
+    for (run_id, pod_ids) in &leftovers.recording {
+        if let Err(err) = stop_recording(metrics_ip.clone(), pod_ids.clone(), *run_id).await {
+            warn!("could not stop recording metrics of run {run_id}: {err}");
+        }
+    }
+    let mut connection = connect_db(&ctx.connect_args.master_ip).await?;
+    let running = leftovers.recording.into_keys().collect::<Vec<_>>();
+    fail_runs(
+        &mut connection,
+        &running,
+        RunStatus::Failed,
+        "benchmark interrupted",
+    )
+    .await?;
+
+    for driver in &ctx.config.benchmark.drivers {
+        delete_bench_job(ctx.cluster, driver).await?;
+    }
+    if ctx.config.parallel_drivers() {
+        release_node_pools().await?;
+    }
+    for (driver, extra_vars) in leftovers.installed {
+        if remove {
+            remove_graph_platform(&driver, ctx.connect_args, extra_vars, ctx.cli.verbose).await?;
+        } else {
+            println!("{driver} is left installed, pass --remove-on-interrupt to remove it");
+        }
+    }
+
+    stop_pod_service(ctx.cluster, "metrics").await?;
+    stop_pod_service(ctx.cluster, "notifier").await?;
+    Ok(())
+}
+
+/// Install a driver, benchmark every dataset on it & remove it again, returning its runs. The
+/// driver is pinned to the nodes of `pool` if drivers run in parallel
+async fn bench_driver(
+    ctx: &BenchContext<'_>,
+    driver: &str,
+    n_nodes: usize,
+    pool: Option<&NodePool>,
+) -> Result<Vec<Run>> {
+    let (config, cluster) = (ctx.config, ctx.cluster);
+    let driver_config = match base_driver::get_driver_config(driver) {
+        Some(d) => d,
+        None => exit!("", "Could not find driver {}", driver),
+    };
+    let mut connection = connect_db(&ctx.connect_args.master_ip).await?;
+    let mut runs = Vec::new();
+    let timeouts = config.benchmark.timeouts.clone().unwrap_or_default();
+    let validate = config.benchmark.validate.unwrap_or(false) && driver_config.writes_output();
+    if config.benchmark.validate.unwrap_or(false) && !validate {
+        info!("{driver} doesn't write the outputs of its runs, they are not validated");
+    }
+
+    let config_hash = config_hash(config, driver, n_nodes)?;
+    info!("{:#?}", config.setup.graph_platform_args);
+    let mut extra_vars = driver_config
+        .scale_service(
+            n_nodes,
+            &cluster.namespace,
+            pool,
+            config
+                .setup
+                .graph_platform_args
+                .as_ref()
+                .unwrap_or(&HashMap::new())
+                .get(driver)
+                .map(|x| x.to_owned()),
+        )
+        .await?;
+    // the playbooks install the driver into the benchmark namespace
+    extra_vars.push(format!("namespace={}", cluster.namespace));
+    setup_graph_platform(
+        driver,
+        ctx.connect_args,
+        &cluster.namespace,
+        n_nodes,
+        extra_vars.clone(),
+        ctx.cli.verbose,
+    )
+    .await?;
+    ctx.leftovers
+        .lock()
+        .await
+        .installed
+        .insert(driver.to_owned(), extra_vars.clone());
+    let platform_config = driver_config
+        .get_platform_config(n_nodes, &cluster.namespace)
+        .await?;
+    let pod_ids = driver_config.metrics_pod_ids(&cluster.namespace).await?;
+    info!("pod ids: {pod_ids:?}");
+    let platform_pods = match config.benchmark.platform_logs.unwrap_or(false) {
+        true => &pod_ids[..],
+        false => &[],
+    };
+
+    let mut cfg = DriverConfig {
+        postgres: POSTGRES_CONFIG,
+        platform: platform_config,
+        dataset: DatasetConfig::default(),
+        config: RunConfig {
+            ids: "".into(),
+            algos: "".into(),
+            log_file: format!("/attached/{driver}.log"),
+            nodes: n_nodes,
+            output_dir: validate.then(|| OUTPUT_DIR.to_owned()),
+        },
+        load_data: true,
+        drop_data: false,
+    };
+
+    for BenchDataset {
+        name: dataset,
+        config: d,
+        algorithms,
+    } in ctx.datasets
+    {
+        println!(
+            "Benchmarking {dataset} on {driver} {} times",
+            config.benchmark.repeat
+        );
+        let algos = driver_algorithms(driver, algorithms);
+        if algos.len() < algorithms.len() {
+            let skipped = algorithms.iter().filter(|x| !algos.contains(x));
+            println!(
+                "{driver} doesn't implement {}, skipping them",
+                skipped.cloned().collect::<Vec<_>>().join(", ")
+            );
+        }
+
+        runs.extend(ctx.journal.lock().await.completed_runs(
+            &config_hash,
+            n_nodes,
+            driver,
+            dataset,
+        ));
+
+        let mut data_loaded = false;
+        for repeat_num in 0..config.benchmark.repeat {
+            let pending = {
+                let journal = ctx.journal.lock().await;
+                algos
+                    .iter()
+                    .filter(|x| {
+                        !journal.is_done(&config_hash, n_nodes, driver, dataset, repeat_num, x)
+                    })
+                    .cloned()
+                    .collect::<Vec<String>>()
             };
-            let runs_start_point = runs.len();
-
-            info!("{:#?}", config.setup.graph_platform_args);
-            let extra_vars = driver_config
-                .scale_service(
-                    n_nodes,
-                    config
-                        .setup
-                        .graph_platform_args
-                        .as_ref()
-                        .unwrap_or(&HashMap::new())
-                        .get(driver)
-                        .map(|x| x.to_owned()),
-                )
-                .await?;
-            setup_graph_platform(
-                &driver,
-                &connect_args,
-                n_nodes,
-                extra_vars.clone(),
-                cli.verbose,
+            if pending.is_empty() {
+                info!("skipping repeat {repeat_num} of {dataset}, already benchmarked");
+                continue;
+            }
+            let pending_ids = new_runs(
+                &mut connection,
+                NewBenchmark {
+                    nodes: n_nodes as i32,
+                    driver,
+                    dataset,
+                    algorithm: "",
+                    repeat: repeat_num as i32,
+                    status: RunStatus::Pending.as_str(),
+                    config_hash: &config_hash,
+                    campaign_id: ctx.campaign_id,
+                },
+                &pending,
             )
             .await?;
-            let platform_config = driver_config.get_platform_config(n_nodes).await?;
-            let pod_ids = driver_config.metrics_pod_ids().await?;
-            info!("pod ids: {pod_ids:?}");
-
-            let mut cfg = DriverConfig {
-                postgres: POSTGRES_CONFIG,
-                platform: platform_config,
-                dataset: DatasetConfig::default(),
-                config: RunConfig {
-                    ids: "".into(),
-                    algos: "".into(),
-                    log_file: "/attached/log".into(),
+            pending.iter().zip(&pending_ids).for_each(|(algo, run_id)| {
+                runs.push(Run {
+                    driver: driver.to_owned(),
+                    dataset: dataset.clone(),
+                    algorithm: algo.clone(),
                     nodes: n_nodes,
-                },
-                load_data: true,
-                drop_data: false,
-            };
-
-            for dataset in &config.benchmark.datasets {
-                let mut algos = config.benchmark.algorithms.clone().unwrap_or(
-                    ALGORITHMS
-                        .iter()
-                        .map(|x| x.to_string())
-                        .collect::<Vec<String>>(),
-                );
+                    repeat: repeat_num,
+                    run_id: *run_id,
+                });
+            });
 
-                println!("Benchmarking {dataset} {} times", config.benchmark.repeat);
-                let d: DatasetUserConfig = toml::from_str(
-                    &fs::read_to_string(format!("datasets/{dataset}/config.toml")).await?,
-                )?;
-                let skip_algos = d.skip_algos.unwrap_or_default();
+            cfg.dataset = DatasetConfig {
+                name: dataset.clone(),
+                vertex: format!("/attached/{dataset}.v"),
+                edges: format!("/attached/{dataset}.e"),
+                weights: d.weights,
+                directed: d.directed,
+                start_vertex: d.start_vertex,
+            };
+            cfg.config.algos = pending.join(",");
+            cfg.config.ids = pending_ids
+                .iter()
+                .map(|x| x.to_string())
+                .collect::<Vec<String>>()
+                .join(",");
+            cfg.load_data = !data_loaded;
+            cfg.drop_data = repeat_num == config.benchmark.repeat - 1;
+            data_loaded = true;
+            info!("{cfg:#?}");
 
-                let run_ids = get_run_ids(&mut connection, n_nodes, algos.len()).await?;
-                run_ids
-                    .iter()
-                    .zip(algos.clone())
-                    .for_each(|(run_id, algo)| {
-                        runs.push(Run {
-                            run_id: *run_id,
-                            dataset: dataset.clone(),
-                            algorithm: algo.clone(),
-                            nodes: n_nodes,
-                        });
-                    });
-
-                for repeat_num in 0..config.benchmark.repeat {
-                    algos = algos
-                        .drain(..)
-                        .filter(|x| !skip_algos.contains(x))
-                        .collect();
-
-                    cfg.dataset = DatasetConfig {
-                        name: dataset.clone(),
-                        vertex: format!("/attached/{dataset}.v"),
-                        edges: format!("/attached/{dataset}.e"),
-                        weights: d.weights,
-                        directed: d.directed,
-                        start_vertex: d.start_vertex,
-                    };
-                    cfg.config.algos = algos.join(",");
-                    cfg.config.ids = run_ids
-                        .iter()
-                        .map(|x| x.to_string())
-                        .collect::<Vec<String>>()
-                        .join(",");
-                    cfg.load_data = repeat_num == 0;
-                    cfg.drop_data = repeat_num == config.benchmark.repeat - 1;
-                    info!("{cfg:#?}");
-
-                    start_bench(
-                        &driver,
-                        &connect_args.master_ip,
-                        &cfg,
-                        nfs_ip.clone(),
-                        config.benchmark.debug.clone().unwrap_or_default().bench_ttl,
-                    )
-                    .await?;
+            // the job is run as a whole so a failure or timeout only fails the runs left in it
+            let mut events = ctx.events.subscribe(&pending_ids);
+            let metrics_ip = format!("http://{}:30001", ctx.connect_args.master_ip);
+            let pods: Api<Pod> = cluster.api(Client::try_default().await?);
+            let k8s_events: Api<Event> = cluster.api(Client::try_default().await?);
+            let mut archive = JobArchive::open(
+                ctx.campaign_name,
+                &pending_ids,
+                &serde_yaml::to_string(&cfg)?,
+            )
+            .await?;
+            let (mut done, mut recording, mut pb) = (0, None, None);
+            let job = async {
+                start_bench(
+                    driver,
+                    cluster,
+                    pool,
+                    &cfg,
+                    ctx.nfs_ip.clone(),
+                    config.benchmark.debug.clone().unwrap_or_default().bench_ttl,
+                )
+                .await?;
+                archive.follow(pods.clone(), bench_selector(driver));
 
-                    let metrics_ip = format!("http://{}:30001", connect_args.master_ip);
-                    for i in 0..run_ids.len() {
-                        let pb = progress(&format!("Benchmarking ({} on {dataset})", algos[i]));
+                let algorithms = async {
+                    while done < pending_ids.len() {
+                        let (algo, run_id) = (&pending[done], pending_ids[done]);
+                        pb = Some(progress(&format!(
+                            "Benchmarking ({algo} on {dataset} with {driver})"
+                        )));
                         let start = Instant::now();
-                        match ws_stream.try_next().await? {
-                            Some(msg) => {
-                                let msg: BenchStartEvent = serde_json::from_str(&msg.into_text()?)?;
-                                if !msg.status {
-                                    exit!(
-                                        "",
-                                        "Expected bench starting message, got bench ending message"
-                                    );
-                                }
-                            }
-                            None => exit!("", "Received incorrect benchmark starting signal"),
-                        }
+                        next_event(&mut events, true).await?;
 
-                        start_recording(metrics_ip.clone(), pod_ids.clone(), run_ids[i]).await?;
+                        set_run_status(&mut connection, run_id, RunStatus::Running).await?;
+                        start_recording(metrics_ip.clone(), pod_ids.clone(), run_id).await?;
+                        recording = Some(run_id);
+                        ctx.leftovers
+                            .lock()
+                            .await
+                            .recording
+                            .insert(run_id, pod_ids.clone());
                         info!("started recording metrics on {metrics_ip}");
+                        let monitor =
+                            PodMonitor::start(pods.clone(), k8s_events.clone(), &pod_ids).await?;
 
-                        ws_stream = wait_for_ws_end_message(
-                            ws_stream,
-                            metrics_ip.clone(),
-                            pod_ids.clone(),
-                            run_ids[i],
+                        within(
+                            timeouts.algorithm,
+                            "algorithm",
+                            next_event(&mut events, false),
                         )
                         .await?;
+                        stop_recording(metrics_ip.clone(), pod_ids.clone(), run_id).await?;
+                        recording = None;
+                        ctx.leftovers.lock().await.recording.remove(&run_id);
+                        info!("stopped recording metrics");
+                        let disruptions = monitor.stop().await?;
+                        set_run_status(&mut connection, run_id, RunStatus::Completed).await?;
+                        record_disruptions(&mut connection, run_id, &disruptions).await?;
+
+                        ctx.journal
+                            .lock()
+                            .await
+                            .record(JournalEntry {
+                                nodes: n_nodes,
+                                driver: driver.to_owned(),
+                                dataset: dataset.clone(),
+                                repeat: repeat_num,
+                                algorithm: algo.clone(),
+                                run_id,
+                                campaign_id: Some(ctx.campaign_id),
+                                config_hash: Some(config_hash.clone()),
+                            })
+                            .await?;
 
                         finish_progress(
                             "Done benchmarking",
-                            &format!("{} on {dataset}", algos[i]),
+                            &format!("{algo} on {dataset} with {driver}"),
                             start.elapsed(),
-                            Some(pb),
+                            pb.take(),
                         );
+                        for d in disruptions.iter().filter(|x| x.taints()) {
+                            println!(
+                                "{} {algo} on {dataset} with {driver} is tainted, {} {}: {}",
+                                style("!").yellow(),
+                                d.pod,
+                                d.kind.as_str(),
+                                d.message.as_deref().or(d.reason.as_deref()).unwrap_or("-"),
+                            );
+                        }
+                        done += 1;
                     }
+                    Ok(())
+                };
+                select! {
+                    res = algorithms => res,
+                    reason = job_failure(cluster, driver) => Err(RunFailure::Failed(reason?)),
+                }
+            };
+            let outcome = within(timeouts.dataset, "dataset", job).await;
+            ctx.events.unsubscribe(&pending_ids);
 
-                    wait_for_bench_delete().await?;
+            if let Err(failure) = outcome {
+                if let Some(run_id) = recording {
+                    if let Err(err) = stop_recording(metrics_ip, pod_ids.clone(), run_id).await {
+                        warn!("could not stop recording metrics of run {run_id}: {err}");
+                    }
+                    ctx.leftovers.lock().await.recording.remove(&run_id);
                 }
+                let algo = &pending[done];
+                fail_progress(
+                    "Failed benchmarking",
+                    &format!("{algo} on {dataset} with {driver}: {failure}"),
+                    pb.take(),
+                );
+                fail_runs(
+                    &mut connection,
+                    &pending_ids[done..=done],
+                    failure.status(),
+                    &failure.to_string(),
+                )
+                .await?;
+                fail_runs(
+                    &mut connection,
+                    &pending_ids[done + 1..],
+                    RunStatus::Failed,
+                    &format!("not run, {algo} failed before it: {failure}"),
+                )
+                .await?;
+                delete_bench_job(cluster, driver).await?;
+                archive
+                    .finish(&pods, platform_pods, Duration::from_secs(5))
+                    .await;
+                if validate {
+                    let (algos, run_ids) = (&pending[..done], &pending_ids[..done]);
+                    validate_runs(&mut connection, &pods, ctx, driver, dataset, algos, run_ids)
+                        .await?;
+                }
+                // the data may be half loaded or dropped, the next job starts over
+                data_loaded = false;
+                continue;
             }
-            visualize_dataset_algos(
-                &runs[runs_start_point..],
-                &driver,
-                n_nodes,
-                connect_args.master_ip.to_string(),
-                nfs_ip.clone(),
-            )
-            .await?;
-            remove_graph_platform(&driver, &connect_args, extra_vars, cli.verbose).await?;
+
+            archive.finish(&pods, platform_pods, LOG_GRACE).await;
+            if validate {
+                validate_runs(
+                    &mut connection,
+                    &pods,
+                    ctx,
+                    driver,
+                    dataset,
+                    &pending,
+                    &pending_ids,
+                )
+                .await?;
+            }
+            wait_for_bench_delete(cluster, driver).await?;
         }
     }
+    remove_graph_platform(driver, ctx.connect_args, extra_vars, ctx.cli.verbose).await?;
+    ctx.leftovers.lock().await.installed.remove(driver);
+    Ok(runs)
+}
 
-    ws_stream.close(None).await?;
+/// Why the runs left in a benchmark job did not complete
+enum RunFailure {
+    Failed(String),
+    TimedOut(String),
+}
 
-    visualize_algos_workers(
-        &runs,
-        &config.benchmark.datasets,
-        connect_args.master_ip.to_string(),
-        nfs_ip.clone(),
-    )
-    .await?;
-    copy_generated_graphs(cli.verbose, &connect_args).await?;
+impl RunFailure {
+    fn status(&self) -> RunStatus {
+        match self {
+            RunFailure::Failed(_) => RunStatus::Failed,
+            RunFailure::TimedOut(_) => RunStatus::TimedOut,
+        }
+    }
+}
+
+impl Display for RunFailure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RunFailure::Failed(reason) | RunFailure::TimedOut(reason) => f.write_str(reason),
+        }
+    }
+}
 
-    stop_pod_service("metrics").await?;
-    stop_pod_service("notifier").await?;
+impl From<anyhow::Error> for RunFailure {
+    fn from(err: anyhow::Error) -> Self {
+        RunFailure::Failed(format!("{err:#}"))
+    }
+}
 
-    Ok(())
+/// Await `fut`, timing out after `limit` seconds if set
+async fn within<T, E>(
+    limit: Option<u64>,
+    what: &str,
+    fut: impl Future<Output = Result<T, E>>,
+) -> Result<T, RunFailure>
+where
+    RunFailure: From<E>,
+{
+    let Some(limit) = limit else {
+        return Ok(fut.await?);
+    };
+    match timeout(Duration::from_secs(limit), fut).await {
+        Ok(res) => Ok(res?),
+        Err(_) => Err(RunFailure::TimedOut(format!(
+            "{what} exceeded its timeout of {limit}s"
+        ))),
+    }
 }
 
-async fn wait_for_ws_end_message(
-    mut ws_stream: WebSocketStream<MaybeTlsStream<TcpStream>>,
-    metrics_ip: String,
-    pod_ids: Vec<String>,
-    run_id: i32,
-) -> Result<WebSocketStream<MaybeTlsStream<TcpStream>>> {
-    match ws_stream.try_next().await? {
-        Some(msg) => {
-            let msg: BenchStartEvent = serde_json::from_str(&msg.into_text()?)?;
-            if msg.status {
-                exit!(
-                    "",
-                    "Expected bench stopping message, got bench starting message"
-                );
-            }
-            stop_recording(metrics_ip, pod_ids, run_id).await?;
-            info!("stopped recording metrics");
-            Ok(ws_stream)
+/// Wait until the benchmark job of a driver fails, returning the reason kubernetes gives for it
+async fn job_failure(cluster: &Cluster, driver: &str) -> Result<String> {
+    let api: Api<Job> = cluster.api(Client::try_default().await?);
+    let job_name = format!("{driver}-bench");
+    loop {
+        let status = api.get_opt(&job_name).await?.and_then(|x| x.status);
+        if let Some(status) = status.filter(|x| x.failed.unwrap_or_default() > 0) {
+            let reason = status
+                .conditions
+                .unwrap_or_default()
+                .into_iter()
+                .find(|x| x.type_ == "Failed")
+                .and_then(|x| x.message);
+            return Ok(reason.unwrap_or_else(|| "benchmark job failed".into()));
         }
-        None => exit!("", "Received incorrect benchmark stopping signal"),
+        sleep(Duration::from_secs(5)).await;
     }
 }
 
-async fn wait_for_bench_delete() -> Result<()> {
+/// Delete the benchmark job of a driver along with its pods & config, failed jobs are not cleaned
+/// up by their ttl before the next one starts
+async fn delete_bench_job(cluster: &Cluster, driver: &str) -> Result<()> {
+    let client = Client::try_default().await?;
+    let name = format!("{driver}-bench");
+    let dp = DeleteParams::background().grace_period(0);
+
+    let jobs: Api<Job> = cluster.api(client.clone());
+    _ = jobs.delete(&name, &dp).await;
+    let pods: Api<Pod> = cluster.api(client.clone());
+    _ = pods
+        .delete_collection(&dp, &ListParams::default().labels(&bench_selector(driver)))
+        .await;
+    let config_maps: Api<ConfigMap> = cluster.api(client);
+    _ = config_maps.delete(&name, &dp).await;
+    Ok(())
+}
+
+/// Label selector of the benchmark jobs of a driver & their pods
+fn bench_selector(driver: &str) -> String {
+    format!("app=graph-bench,driver={driver}")
+}
+
+async fn wait_for_bench_delete(cluster: &Cluster, driver: &str) -> Result<()> {
     let client = Client::try_default().await?;
-    let api: Api<Job> = Api::default_namespaced(client);
+    let api: Api<Job> = cluster.api(client);
 
     let mut job_stream = api
-        .watch(&WatchParams::default().labels("app=graph-bench"), "0")
+        .watch(&WatchParams::default().labels(&bench_selector(driver)), "0")
         .await
         .unwrap()
         .boxed();
@@ -340,39 +765,30 @@ async fn wait_for_bench_delete() -> Result<()> {
     Ok(())
 }
 
+/// Resize the cluster to the nodes needed to benchmark on `n_nodes` nodes per driver, and split
+/// them into a pool for every driver if they run in parallel
 async fn new_cluster_node_count(
     n_nodes: usize,
+    cluster: &Cluster,
     connect_args: &PlatformInfo,
     config: &Config,
     verbose: bool,
-) -> Result<()> {
+) -> Result<Vec<NodePool>> {
     let client = Client::try_default().await?;
     let api: Api<Node> = Api::all(client);
     let mut nodes = api.list(&ListParams::default()).await?;
-    let get_idx = |n: &str| {
-        if n.starts_with("worker-") {
-            n.split("worker-").last().unwrap().parse::<i32>().unwrap()
-        } else {
-            -1
-        }
-    };
+    nodes.items.sort_by(|a, b| {
+        let a_name = a.metadata.name.as_ref().unwrap();
+        let b_name = b.metadata.name.as_ref().unwrap();
 
-    if nodes.items.len() > n_nodes {
-        nodes.items.sort_by(|a, b| {
-            let a_name = a.metadata.name.as_ref().unwrap();
-            let b_name = b.metadata.name.as_ref().unwrap();
-
-            get_idx(a_name).cmp(&get_idx(b_name))
-        });
+        (worker_index(a_name), a_name).cmp(&(worker_index(b_name), b_name))
+    });
 
-        let curr_nodes_len = nodes.items.len();
-        let delete_nodes = nodes
-            .items
-            .drain(..)
-            .rev()
-            .take(curr_nodes_len - n_nodes)
-            .collect::<Vec<_>>();
-        for node in delete_nodes {
+    let cluster_size = config.cluster_size(n_nodes);
+    // the nodes of an existing cluster are not ours to remove
+    if !cluster.existing && nodes.items.len() > cluster_size {
+        let delete_nodes = nodes.items.split_off(cluster_size);
+        for node in delete_nodes.into_iter().rev() {
             let name = node.metadata.name.unwrap();
             info!("Removing node {name}");
             api.delete(&name, &DeleteParams::default()).await?;
@@ -390,23 +806,88 @@ async fn new_cluster_node_count(
             .await?;
         }
     }
+
+    if !config.parallel_drivers() {
+        return Ok(Vec::new());
+    }
+    let workers = nodes
+        .items
+        .into_iter()
+        .filter(|x| {
+            let labels = x.metadata.labels.clone().unwrap_or_default();
+            !labels.contains_key("node-role.kubernetes.io/master")
+                && !labels.contains_key("node-role.kubernetes.io/control-plane")
+        })
+        .filter_map(|x| x.metadata.name)
+        .collect::<Vec<_>>();
+    let drivers = &config.benchmark.drivers;
+    if workers.len() < n_nodes * drivers.len() {
+        exit!(
+            "",
+            "Need {} worker nodes for pools of {} nodes for {} drivers, the cluster has {}",
+            n_nodes * drivers.len(),
+            n_nodes,
+            drivers.len(),
+            workers.len()
+        );
+    }
+
+    let pools = drivers
+        .iter()
+        .zip(workers.chunks(n_nodes))
+        .map(|(driver, nodes)| NodePool {
+            name: driver.clone(),
+            nodes: nodes.to_vec(),
+        })
+        .collect::<Vec<_>>();
+    for (i, node) in workers.iter().enumerate() {
+        let pool = pools.get(i / n_nodes).map(|x| x.name.as_str());
+        set_node_pool(&api, node, pool).await?;
+    }
+    for pool in &pools {
+        println!("Node pool of {}: {}", pool.name, pool.nodes.join(", "));
+    }
+    Ok(pools)
+}
+
+/// Index of a k3s worker node named `worker-<i>`, the master & other nodes sort before them
+fn worker_index(name: &str) -> i32 {
+    name.strip_prefix("worker-")
+        .and_then(|x| x.parse().ok())
+        .unwrap_or(-1)
+}
+
+/// Assign a node to the pool of a driver, or take it out of any pool
+async fn set_node_pool(api: &Api<Node>, node: &str, pool: Option<&str>) -> Result<()> {
+    let patch = serde_json::json!({ "metadata": { "labels": { POOL_LABEL: pool } } });
+    api.patch(node, &PatchParams::default(), &Patch::Merge(&patch))
+        .await?;
+    Ok(())
+}
+
+/// Take every node out of the pools of the drivers, once they are benchmarked
+async fn release_node_pools() -> Result<()> {
+    let api: Api<Node> = Api::all(Client::try_default().await?);
+    let nodes = api.list(&ListParams::default().labels(POOL_LABEL)).await?;
+    for node in nodes.items.into_iter().filter_map(|x| x.metadata.name) {
+        set_node_pool(&api, &node, None).await?;
+    }
     Ok(())
 }
 
 async fn setup_graph_platform(
     name: &str,
     connect_args: &PlatformInfo,
+    namespace: &str,
     nodes: usize,
     extra_vars: Vec<String>,
     verbose: bool,
 ) -> Result<()> {
-    let mut args = vec![
-        "setup.yaml",
-        "--private-key",
-        &connect_args.ssh_key,
-        "-i",
-        "../../k3s/inventory/master-hosts.yaml",
-    ];
+    let mut args = vec!["setup.yaml", "-i", "../../k3s/inventory/master-hosts.yaml"];
+    // the playbooks run locally against an existing cluster
+    if !connect_args.ssh_key.is_empty() {
+        args.extend(["--private-key", &connect_args.ssh_key]);
+    }
 
     let extra_vars_str = extra_vars.join(" ");
     if extra_vars.len() > 0 {
@@ -414,18 +895,22 @@ async fn setup_graph_platform(
         args.push(&extra_vars_str);
     }
 
-    command_print(
-        "ansible-playbook",
-        &args,
-        verbose,
-        [
-            &format!("Installing {name}"),
-            &format!("Could not install {name}"),
-            &format!("Installed {name}"),
-        ],
-        &format!("drivers/{}", name),
-        HashMap::from([("ANSIBLE_HOST_KEY_CHECKING", "False")]),
-    )
+    let msgs = [
+        format!("Installing {name}"),
+        format!("Could not install {name}"),
+        format!("Installed {name}"),
+    ];
+    let dir = format!("drivers/{}", name);
+    retry(RetryStep::Ansible, &format!("install {name}"), || {
+        command_print(
+            "ansible-playbook",
+            &args,
+            verbose,
+            msgs.each_ref().map(|x| x.as_str()),
+            &dir,
+            HashMap::from([("ANSIBLE_HOST_KEY_CHECKING", "False")]),
+        )
+    })
     .await?;
 
     let start = Instant::now();
@@ -433,7 +918,7 @@ async fn setup_graph_platform(
 
     base_driver::get_driver_config(name)
         .unwrap()
-        .wait_for_service_ready(nodes)
+        .wait_for_service_ready(nodes, namespace)
         .await?;
 
     finish_progress(
@@ -447,7 +932,8 @@ async fn setup_graph_platform(
 
 async fn start_bench(
     name: &str,
-    host_ip: &IpAddr,
+    cluster: &Cluster,
+    pool: Option<&NodePool>,
     cfg: &DriverConfig<'_>,
     nfs_ip: String,
     bench_ttl: Option<i32>,
@@ -461,7 +947,7 @@ async fn start_bench(
 
     let job_name = format!("{}-bench", name);
 
-    let config_map: Api<ConfigMap> = Api::default_namespaced(client.clone());
+    let config_map: Api<ConfigMap> = cluster.api(client.clone());
     _ = config_map
         .delete(&job_name, &DeleteParams::default().grace_period(0))
         .await;
@@ -475,21 +961,18 @@ async fn start_bench(
     config_map_spec.immutable = Some(true);
     config_map.create(&default_pp, &config_map_spec).await?;
 
-    let c = client.clone();
-    let wait_for_job_close = spawn(async {
-        let jobs: Api<Job> = Api::default_namespaced(c);
-
-        if let Ok(jobs) = jobs
-            .list(&ListParams::default().labels("app=graph-bench"))
-            .await
-        {
+    let selector = bench_selector(name);
+    let jobs: Api<Job> = cluster.api(client.clone());
+    let s = selector.clone();
+    let wait_for_job_close = spawn(async move {
+        if let Ok(jobs) = jobs.list(&ListParams::default().labels(&s)).await {
             if jobs.items.len() == 0 {
                 return;
             }
         }
 
         let mut job_stream = jobs
-            .watch(&WatchParams::default().labels("app=graph-bench"), "0")
+            .watch(&WatchParams::default().labels(&s), "0")
             .await
             .unwrap()
             .boxed();
@@ -502,25 +985,29 @@ async fn start_bench(
         info!("old jobs deleted!");
     });
 
-    let jobs: Api<Job> = Api::default_namespaced(client.clone());
+    let jobs: Api<Job> = cluster.api(client.clone());
     _ = jobs
         .delete(&job_name, &DeleteParams::default().grace_period(0))
         .await;
 
-    let pods: Api<Pod> = Api::default_namespaced(client);
+    let pods: Api<Pod> = cluster.api(client);
     _ = pods
         .delete_collection(
             &DeleteParams::default().grace_period(0),
-            &ListParams::default().labels("app=graph-bench"),
+            &ListParams::default().labels(&selector),
         )
         .await;
 
     wait_for_job_close.await?;
 
+    let labels = BTreeMap::from([
+        ("app".to_owned(), "graph-bench".to_owned()),
+        ("driver".to_owned(), name.to_owned()),
+    ]);
     let mut job_spec = Job::default();
     job_spec.metadata = ObjectMeta {
         name: Some(job_name.clone()),
-        labels: Some(BTreeMap::from([("app".into(), "graph-bench".into())])),
+        labels: Some(labels.clone()),
         ..Default::default()
     };
     job_spec.spec = Some(JobSpec {
@@ -528,15 +1015,17 @@ async fn start_bench(
         ttl_seconds_after_finished: bench_ttl,
         template: PodTemplateSpec {
             metadata: Some(ObjectMeta {
-                labels: Some(BTreeMap::from([("app".into(), "graph-bench".into())])),
+                labels: Some(labels.clone()),
                 ..Default::default()
             }),
             spec: Some(PodSpec {
                 service_account_name: Some("admin-user".into()),
                 restart_policy: Some("Never".into()),
+                node_selector: pool
+                    .map(|x| BTreeMap::from([(POOL_LABEL.to_owned(), x.name.clone())])),
                 containers: vec![Container {
                     args: Some(vec!["/cfg/config.yaml".into()]),
-                    image: Some(format!("{host_ip}:30000/benches/{name}:latest")),
+                    image: Some(format!("{}/benches/{name}:latest", cluster.registry)),
                     image_pull_policy: Some("Always".into()),
                     name: format!("{}-bench", name).into(),
                     volume_mounts: Some(vec![
@@ -598,6 +1087,74 @@ async fn start_bench(
     Ok(())
 }
 
+/// Read the dataset configs, resolving the algorithms to run on each of them
+pub(crate) async fn load_datasets(config: &Config) -> Result<Vec<BenchDataset>> {
+    let mut datasets = Vec::new();
+    for dataset in &config.benchmark.datasets {
+        let file = format!("datasets/{dataset}/config.toml");
+        let d: DatasetUserConfig = match fs::read_to_string(&file).await {
+            Ok(s) => toml::from_str(&s)?,
+            Err(err) => exit!(err, "Could not read dataset config {file}"),
+        };
+        let skip_algos = d.skip_algos.clone().unwrap_or_default();
+        let algorithms = config
+            .benchmark
+            .algorithms
+            .clone()
+            .unwrap_or(
+                ALGORITHMS
+                    .iter()
+                    .map(|x| x.to_string())
+                    .collect::<Vec<String>>(),
+            )
+            .into_iter()
+            .filter(|x| !skip_algos.contains(x))
+            .collect::<Vec<String>>();
+        datasets.push(BenchDataset {
+            name: dataset.clone(),
+            config: d,
+            algorithms,
+        });
+    }
+    Ok(datasets)
+}
+
+/// The algorithms of a dataset the driver implements
+pub(crate) fn driver_algorithms(driver: &str, algorithms: &[String]) -> Vec<String> {
+    let Some(d) = base_driver::get_driver_config(driver) else {
+        return algorithms.to_vec();
+    };
+    algorithms
+        .iter()
+        .filter(|x| d.algorithms().contains(&x.as_str()))
+        .cloned()
+        .collect()
+}
+
+/// Get the connection information of the configured provider
+pub(crate) async fn platform_info(config: &Config, verbose: bool) -> Result<PlatformInfo> {
+    for p in base_provider::PROVIDERS {
+        if p.name() == config.setup.provider {
+            let info = p.platform_info(&config.setup, verbose).await?;
+            use_kube_config(config)?;
+            if config.existing_cluster() {
+                forward_services(config.namespace()).await?;
+            }
+            return Ok(info);
+        }
+    }
+    exit!("", "Unknown platform {}", config.setup.platform)
+}
+
+/// Connect to the benchmark postgres database through its node port
+pub(crate) async fn connect_db(master_ip: &IpAddr) -> Result<AsyncPgConnection> {
+    Ok(AsyncPgConnection::establish(&format!(
+        "postgres://{}:{}@{}:30002/{}",
+        POSTGRES_CONFIG.user, POSTGRES_CONFIG.ps, master_ip, POSTGRES_CONFIG.db
+    ))
+    .await?)
+}
+
 fn setup_db(master_ip: IpAddr) -> Result<()> {
     std::thread::spawn(move || {
         let mut connection = AsyncConnectionWrapper::<AsyncPgConnection>::establish(&format!(
@@ -612,17 +1169,38 @@ fn setup_db(master_ip: IpAddr) -> Result<()> {
     Ok(())
 }
 
-async fn get_run_ids(
+/// Hash of the parts of the configuration that change the measured setup of a run: the provider,
+/// the machines, the number of nodes & the graph platform arguments of the driver. Stored with
+/// every run to tell apart results produced by different setups, whatever the campaign or the
+/// datasets they belong to
+fn config_hash(config: &Config, driver: &str, n_nodes: usize) -> Result<String> {
+    let setup = &config.setup;
+    let measured = serde_json::json!({
+        "provider": setup.provider,
+        "platform": setup.platform,
+        "nodes": n_nodes,
+        "master_platform": setup.master_platform,
+        "worker_platform": setup.worker_platform,
+        "platform_args": setup.platform_args,
+        "graph_platform_args": setup.graph_platform_args.as_ref().and_then(|x| x.get(driver)),
+    });
+    let mut hasher = Sha256::new();
+    hasher.update(serde_json::to_string(&measured)?);
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+/// Insert a pending run for each algorithm, the other columns are taken from `run`
+async fn new_runs(
     conn: &mut AsyncPgConnection,
-    n_nodes: usize,
-    count: usize,
+    run: NewBenchmark<'_>,
+    algorithms: &[String],
 ) -> Result<Vec<i32>> {
-    use crate::schema::benchmarks::{self, dsl::*};
+    use crate::schema::benchmarks;
     let b = diesel::insert_into(benchmarks::table)
         .values(
-            (0..count)
-                .into_iter()
-                .map(|_| nodes.eq(n_nodes as i32))
+            algorithms
+                .iter()
+                .map(|algorithm| NewBenchmark { algorithm, ..run })
                 .collect::<Vec<_>>(),
         )
         .returning(Benchmark::as_returning())
@@ -630,3 +1208,193 @@ async fn get_run_ids(
         .await?;
     Ok(b.into_iter().map(|x| x.id).collect())
 }
+
+/// Mark runs that did not complete, stamping the end time & why they did not
+async fn fail_runs(
+    conn: &mut AsyncPgConnection,
+    run_ids: &[i32],
+    run_status: RunStatus,
+    reason: &str,
+) -> Result<()> {
+    use crate::schema::benchmarks::dsl::*;
+    diesel::update(benchmarks.filter(id.eq_any(run_ids)))
+        .set((
+            status.eq(run_status.as_str()),
+            status_reason.eq(reason),
+            finished_at.eq(now),
+        ))
+        .execute(conn)
+        .await?;
+    Ok(())
+}
+
+/// Store what happened to the platform pods during a run, tainting it if any of it may have
+/// skewed its results
+async fn record_disruptions(
+    conn: &mut AsyncPgConnection,
+    run_id: i32,
+    disruptions: &[Disruption],
+) -> Result<()> {
+    use crate::schema::{benchmarks, run_events};
+    if disruptions.is_empty() {
+        return Ok(());
+    }
+
+    diesel::insert_into(run_events::table)
+        .values(
+            disruptions
+                .iter()
+                .map(|x| NewRunEvent {
+                    run_id,
+                    pod: &x.pod,
+                    kind: x.kind.as_str(),
+                    reason: x.reason.as_deref(),
+                    message: x.message.as_deref(),
+                    occurred_at: x.at.naive_utc(),
+                })
+                .collect::<Vec<_>>(),
+        )
+        .execute(conn)
+        .await?;
+    if disruptions.iter().any(Disruption::taints) {
+        diesel::update(benchmarks::table.find(run_id))
+            .set(benchmarks::tainted.eq(true))
+            .execute(conn)
+            .await?;
+    }
+    Ok(())
+}
+
+/// Compare the outputs of the runs of a job with the reference outputs of the dataset, for the
+/// algorithms that have one
+async fn validate_runs(
+    conn: &mut AsyncPgConnection,
+    pods: &Api<Pod>,
+    ctx: &BenchContext<'_>,
+    driver: &str,
+    dataset: &str,
+    algos: &[String],
+    run_ids: &[i32],
+) -> Result<()> {
+    use crate::schema::benchmarks::dsl::{benchmarks, validation, validation_message};
+    let nfs_pod = running_pod(pods, "app=nfs-server").await?;
+    for (algo, run_id) in algos.iter().zip(run_ids) {
+        let Some(reference) = reference_file(dataset, algo) else {
+            info!("no reference output of {algo} on {dataset}, not validating run {run_id}");
+            continue;
+        };
+        let (outcome, message) = match validate_run(
+            pods,
+            &nfs_pod,
+            ctx.campaign_name,
+            *run_id,
+            algo,
+            &reference,
+        )
+        .await
+        {
+            Ok(x) => x,
+            Err(err) => {
+                warn!("could not validate run {run_id}: {err:#}");
+                continue;
+            }
+        };
+        diesel::update(benchmarks.find(run_id))
+            .set((
+                validation.eq(outcome.as_str()),
+                validation_message.eq(message.as_deref()),
+            ))
+            .execute(conn)
+            .await?;
+
+        if outcome != Validation::Passed {
+            println!(
+                "{} {algo} on {dataset} with {driver} {}: {}",
+                style("!").red(),
+                match outcome {
+                    Validation::Failed => "failed validation",
+                    _ => "could not be validated",
+                },
+                message.unwrap_or_default()
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Update the status of a run, stamping the start or end time along with it
+async fn set_run_status(
+    conn: &mut AsyncPgConnection,
+    run_id: i32,
+    run_status: RunStatus,
+) -> Result<()> {
+    use crate::schema::benchmarks::dsl::*;
+    let run = diesel::update(benchmarks.find(run_id));
+    match run_status {
+        RunStatus::Running => {
+            run.set((status.eq(run_status.as_str()), started_at.eq(now)))
+                .execute(conn)
+                .await?
+        }
+        _ => {
+            run.set((status.eq(run_status.as_str()), finished_at.eq(now)))
+                .execute(conn)
+                .await?
+        }
+    };
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(node_configs: &str, graphscope_cpu: u32) -> Config {
+        toml::from_str(&format!(
+            r#"
+            [setup]
+            provider = "vagrant"
+            platform = "vagrant-libvirt"
+            node_configs = {node_configs}
+
+            [setup.graph_platform_args.graphscope]
+            cpu = {graphscope_cpu}
+
+            [setup.graph_platform_args.neo4j]
+            memory = "2Gi"
+
+            [benchmark]
+            drivers = ["graphscope", "neo4j"]
+            datasets = ["tiny"]
+            repeat = 1
+            "#
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn config_hash_ignores_other_node_configs() {
+        let hash = config_hash(&config("[2]", 4), "graphscope", 2).unwrap();
+        assert_eq!(
+            hash,
+            config_hash(&config("[2, 4, 10]", 4), "graphscope", 2).unwrap()
+        );
+        assert_ne!(
+            hash,
+            config_hash(&config("[2, 4, 10]", 4), "graphscope", 4).unwrap()
+        );
+    }
+
+    #[test]
+    fn config_hash_follows_the_args_of_the_driver() {
+        let hash = config_hash(&config("[2]", 4), "graphscope", 2).unwrap();
+        assert_ne!(
+            hash,
+            config_hash(&config("[2]", 8), "graphscope", 2).unwrap()
+        );
+        assert_eq!(
+            config_hash(&config("[2]", 4), "neo4j", 2).unwrap(),
+            config_hash(&config("[2]", 8), "neo4j", 2).unwrap()
+        );
+    }
+}
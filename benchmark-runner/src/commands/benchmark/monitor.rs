@@ -0,0 +1,211 @@
+use std::collections::{HashMap, HashSet};
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use futures_util::TryStreamExt;
+use k8s_openapi::api::core::v1::{Event, Pod};
+use kube::{
+    runtime::{watcher, WatchStreamExt},
+    Api, ResourceExt,
+};
+use tokio::{
+    select, spawn,
+    sync::oneshot::{channel, Sender},
+    task::JoinHandle,
+};
+use tracing::{info, warn};
+
+use crate::model::RunEventKind;
+
+/// Reasons of warning events that mean a pod was disrupted, rather than eg. a slow probe
+const DISRUPTING_REASONS: &[&str] = &[
+    "Evicted",
+    "OOMKilling",
+    "Killing",
+    "Preempting",
+    "NodeNotReady",
+];
+
+/// Something that happened to a platform pod while a run was recorded
+#[derive(Debug, Clone)]
+pub struct Disruption {
+    pub pod: String,
+    pub kind: RunEventKind,
+    pub reason: Option<String>,
+    pub message: Option<String>,
+    pub at: DateTime<Utc>,
+}
+
+impl Disruption {
+    /// Whether the results of the run can no longer be trusted
+    pub fn taints(&self) -> bool {
+        match self.kind {
+            RunEventKind::Warning => self
+                .reason
+                .as_deref()
+                .is_some_and(|x| DISRUPTING_REASONS.contains(&x)),
+            _ => true,
+        }
+    }
+}
+
+/// Watches the platform pods of a run for restarts, OOM kills, evictions & warning events, from
+/// the start to the end of its recording
+pub struct PodMonitor {
+    pods: Api<Pod>,
+    pod_ids: Vec<String>,
+    /// Restart count of every container of the pods when the run started
+    restarts: HashMap<(String, String), i32>,
+    stop: Sender<()>,
+    events: JoinHandle<Vec<Event>>,
+}
+
+impl PodMonitor {
+    pub async fn start(pods: Api<Pod>, events: Api<Event>, pod_ids: &[String]) -> Result<Self> {
+        let started = Utc::now();
+        let mut restarts = HashMap::new();
+        for id in pod_ids {
+            if let Some(pod) = pods.get_opt(id).await? {
+                for (container, count) in restart_counts(&pod) {
+                    restarts.insert((id.clone(), container), count);
+                }
+            }
+        }
+
+        let (stop, mut stopped) = channel();
+        let names = pod_ids.iter().cloned().collect::<HashSet<_>>();
+        let events = spawn(async move {
+            let mut seen = HashMap::new();
+            let mut stream = Box::pin(
+                watcher(
+                    events,
+                    watcher::Config::default().fields("involvedObject.kind=Pod"),
+                )
+                .default_backoff()
+                .applied_objects(),
+            );
+            loop {
+                select! {
+                    _ = &mut stopped => break,
+                    event = stream.try_next() => match event {
+                        Ok(Some(event)) => {
+                            let relevant = event.type_.as_deref() == Some("Warning")
+                                && event
+                                    .involved_object
+                                    .name
+                                    .as_ref()
+                                    .is_some_and(|x| names.contains(x))
+                                && event_time(&event).is_some_and(|x| x >= started);
+                            if relevant {
+                                // a repeated event is updated in place, keep its latest state
+                                seen.insert(event.uid().unwrap_or_default(), event);
+                            }
+                        }
+                        Ok(None) => break,
+                        Err(err) => warn!("could not watch the events of the platform pods: {err}"),
+                    }
+                }
+            }
+            seen.into_values().collect()
+        });
+
+        Ok(Self {
+            pods,
+            pod_ids: pod_ids.to_vec(),
+            restarts,
+            stop,
+            events,
+        })
+    }
+
+    /// Stop watching, returning what happened to the pods since the start
+    pub async fn stop(self) -> Result<Vec<Disruption>> {
+        _ = self.stop.send(());
+        let mut disruptions = self
+            .events
+            .await?
+            .into_iter()
+            .map(|x| Disruption {
+                pod: x.involved_object.name.clone().unwrap_or_default(),
+                kind: RunEventKind::Warning,
+                at: event_time(&x).unwrap_or_else(Utc::now),
+                reason: x.reason,
+                message: x.message,
+            })
+            .collect::<Vec<_>>();
+
+        for id in &self.pod_ids {
+            let Some(pod) = self.pods.get_opt(id).await? else {
+                disruptions.push(Disruption {
+                    pod: id.clone(),
+                    kind: RunEventKind::Missing,
+                    reason: None,
+                    message: Some("pod was deleted or rescheduled during the run".into()),
+                    at: Utc::now(),
+                });
+                continue;
+            };
+            let status = pod.status.clone().unwrap_or_default();
+            if status.reason.as_deref() == Some("Evicted") {
+                disruptions.push(Disruption {
+                    pod: id.clone(),
+                    kind: RunEventKind::Evicted,
+                    reason: status.reason.clone(),
+                    message: status.message.clone(),
+                    at: Utc::now(),
+                });
+            }
+            for container in status.container_statuses.unwrap_or_default() {
+                let before = self
+                    .restarts
+                    .get(&(id.clone(), container.name.clone()))
+                    .copied()
+                    .unwrap_or_default();
+                let restarts = container.restart_count - before;
+                if restarts <= 0 {
+                    continue;
+                }
+                let terminated = container.last_state.and_then(|x| x.terminated);
+                let reason = terminated.as_ref().and_then(|x| x.reason.clone());
+                disruptions.push(Disruption {
+                    pod: id.clone(),
+                    kind: match reason.as_deref() {
+                        Some("OOMKilled") => RunEventKind::OomKilled,
+                        _ => RunEventKind::Restart,
+                    },
+                    reason,
+                    message: Some(format!(
+                        "container {} restarted {restarts} times",
+                        container.name
+                    )),
+                    at: terminated
+                        .and_then(|x| x.finished_at)
+                        .map(|x| x.0)
+                        .unwrap_or_else(Utc::now),
+                });
+            }
+        }
+        info!("platform pod disruptions: {disruptions:?}");
+        Ok(disruptions)
+    }
+}
+
+fn restart_counts(pod: &Pod) -> Vec<(String, i32)> {
+    pod.status
+        .as_ref()
+        .and_then(|x| x.container_statuses.as_ref())
+        .map(|x| {
+            x.iter()
+                .map(|x| (x.name.clone(), x.restart_count))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn event_time(event: &Event) -> Option<DateTime<Utc>> {
+    event
+        .last_timestamp
+        .as_ref()
+        .map(|x| x.0)
+        .or(event.event_time.as_ref().map(|x| x.0))
+}
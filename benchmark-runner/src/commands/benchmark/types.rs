@@ -4,9 +4,11 @@ use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Run {
+    pub driver: String,
     pub dataset: String,
     pub algorithm: String,
     pub nodes: usize,
+    pub repeat: usize,
     pub run_id: i32,
 }
 
@@ -18,6 +20,14 @@ pub struct DatasetUserConfig {
     pub skip_algos: Option<Vec<String>>,
 }
 
+/// A dataset to benchmark, with the algorithms left to run on it after `skip_algos`
+#[derive(Debug, Clone)]
+pub struct BenchDataset {
+    pub name: String,
+    pub config: DatasetUserConfig,
+    pub algorithms: Vec<String>,
+}
+
 #[derive(Default, Debug, Clone, Serialize, Deserialize)]
 pub struct DatasetConfig {
     pub vertex: String,
@@ -34,6 +44,9 @@ pub struct RunConfig {
     pub algos: String,
     pub log_file: String,
     pub nodes: usize,
+    /// Directory the driver writes the output of every run to, in a file named after the run id
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output_dir: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -59,4 +72,7 @@ pub struct PostgresConfig<'a> {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BenchStartEvent {
     pub status: bool,
+    /// Run the event is about, if the driver reports it
+    #[serde(default)]
+    pub id: Option<i32>,
 }
@@ -0,0 +1,229 @@
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{bail, Result};
+use k8s_openapi::api::core::v1::Pod;
+use kube::{api::AttachParams, Api};
+use tokio::{fs, io::AsyncReadExt};
+
+use super::logs::run_dir;
+use crate::model::Validation;
+
+/// Where the drivers write the outputs of the runs, on the bench-storage volume
+pub const OUTPUT_DIR: &str = "/attached/output";
+/// The same directory in the NFS server pod
+const NFS_OUTPUT_DIR: &str = "/exports/bench-storage/output";
+/// Relative difference allowed between a floating point value & its reference
+const EPSILON: f64 = 1e-4;
+
+/// Reference output of an algorithm on a dataset, `datasets/<name>/<name>-<algo>`, or with the
+/// algorithm in upper case as in the Graphalytics datasets
+pub fn reference_file(dataset: &str, algo: &str) -> Option<PathBuf> {
+    [algo.to_owned(), algo.to_uppercase()]
+        .into_iter()
+        .map(|x| {
+            Path::new("datasets")
+                .join(dataset)
+                .join(format!("{dataset}-{x}"))
+        })
+        .find(|x| x.is_file())
+}
+
+/// Validate the output a driver wrote for a run against its reference, removing it from the
+/// bench-storage volume. The output of a failed run is kept with its logs
+pub async fn validate_run(
+    pods: &Api<Pod>,
+    nfs_pod: &str,
+    campaign: &str,
+    run_id: i32,
+    algo: &str,
+    reference: &Path,
+) -> Result<(Validation, Option<String>)> {
+    let Some(output) = take_output(pods, nfs_pod, run_id).await? else {
+        return Ok((
+            Validation::Missing,
+            Some("the driver wrote no output".into()),
+        ));
+    };
+    let expected = fs::read_to_string(reference).await?;
+    match compare(algo, &expected, &output) {
+        Ok(()) => Ok((Validation::Passed, None)),
+        Err(diff) => {
+            let dir = run_dir(campaign, run_id);
+            fs::create_dir_all(&dir).await?;
+            fs::write(dir.join("output"), output).await?;
+            Ok((Validation::Failed, Some(diff)))
+        }
+    }
+}
+
+/// Read & delete the output of a run from the NFS server, `None` if there is none
+async fn take_output(pods: &Api<Pod>, nfs_pod: &str, run_id: i32) -> Result<Option<String>> {
+    let file = format!("{NFS_OUTPUT_DIR}/{run_id}");
+    let mut process = pods
+        .exec(
+            nfs_pod,
+            ["sh", "-c", &format!("cat {file} && rm -f {file}")],
+            &AttachParams::default().stderr(false),
+        )
+        .await?;
+    let mut output = String::new();
+    if let Some(mut stdout) = process.stdout() {
+        stdout.read_to_string(&mut output).await?;
+    }
+    let status = match process.take_status() {
+        Some(status) => status.await,
+        None => None,
+    };
+    process.join().await?;
+    match status.and_then(|x| x.status) {
+        Some(x) if x == "Success" => Ok(Some(output)),
+        _ => Ok(None),
+    }
+}
+
+/// Compare an output with its reference, describing the differences if they don't match. Floating
+/// point results may differ by [`EPSILON`], and the labels of components & communities only have
+/// to group the same vertices
+fn compare(algo: &str, expected: &str, actual: &str) -> Result<(), String> {
+    let expected = parse(expected).map_err(|x| format!("invalid reference: {x}"))?;
+    let actual = parse(actual).map_err(|x| format!("invalid output: {x}"))?;
+
+    let missing = expected.keys().filter(|x| !actual.contains_key(x)).count();
+    let extra = actual.keys().filter(|x| !expected.contains_key(x)).count();
+    if missing > 0 || extra > 0 {
+        return Err(format!(
+            "{missing} vertices are missing from the output and {extra} are not in the reference"
+        ));
+    }
+
+    let mut labels = (HashMap::new(), HashMap::new());
+    let mut diffs = expected.iter().filter(|(vertex, e)| {
+        let a = actual[*vertex];
+        match algo {
+            "pr" | "lcc" | "sssp" => !close(e, a),
+            "wcc" | "cdlp" => {
+                // a label has to stand for the same group of vertices in both
+                *labels.0.entry(**e).or_insert(a) != a || *labels.1.entry(a).or_insert(**e) != **e
+            }
+            _ => !same_integer(e, a),
+        }
+    });
+    let Some((vertex, value)) = diffs.next() else {
+        return Ok(());
+    };
+    Err(format!(
+        "vertex {vertex} is {} instead of {value}, {} of {} vertices differ",
+        actual[vertex],
+        diffs.count() + 1,
+        expected.len()
+    ))
+}
+
+/// Values by vertex id of the `<vertex> <value>` lines of an output
+fn parse(output: &str) -> Result<HashMap<u64, &str>> {
+    let mut values = HashMap::new();
+    for (n, line) in output.lines().enumerate() {
+        let mut fields = line.split_ascii_whitespace();
+        let (vertex, value) = match (fields.next(), fields.next()) {
+            (None, _) => continue,
+            (Some(vertex), Some(value)) => (vertex, value),
+            (Some(_), None) => bail!("no value on line {}", n + 1),
+        };
+        let Ok(vertex) = vertex.parse::<u64>() else {
+            bail!("invalid vertex {vertex} on line {}", n + 1);
+        };
+        values.insert(vertex, value);
+    }
+    Ok(values)
+}
+
+fn same_integer(expected: &str, actual: &str) -> bool {
+    expected == actual
+        || matches!(
+            (expected.parse::<i64>(), actual.parse::<i64>()),
+            (Ok(e), Ok(a)) if e == a
+        )
+}
+
+fn close(expected: &str, actual: &str) -> bool {
+    let (Ok(e), Ok(a)) = (expected.parse::<f64>(), actual.parse::<f64>()) else {
+        return false;
+    };
+    // unreachable vertices are infinitely far, only an infinity matches them
+    e == a || (e.is_finite() && a.is_finite() && (e - a).abs() <= EPSILON * e.abs().max(a.abs()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_lines() {
+        let values = parse("1 0.5\n\n2 3 extra\n").unwrap();
+        assert_eq!(values, HashMap::from([(1, "0.5"), (2, "3")]));
+        assert_eq!(
+            parse("1 2\n3\n").unwrap_err().to_string(),
+            "no value on line 2"
+        );
+        assert_eq!(
+            parse("a 2\n").unwrap_err().to_string(),
+            "invalid vertex a on line 1"
+        );
+    }
+
+    #[test]
+    fn integers_are_equal() {
+        let expected = "1 0\n2 1\n3 9223372036854775807\n";
+        assert_eq!(
+            compare("bfs", expected, "3 9223372036854775807\n1 0\n2 1\n"),
+            Ok(())
+        );
+        assert_eq!(
+            compare("bfs", expected, "1 0\n2 2\n3 9223372036854775807\n"),
+            Err("vertex 2 is 2 instead of 1, 1 of 3 vertices differ".to_owned())
+        );
+    }
+
+    #[test]
+    fn floats_within_epsilon() {
+        let expected = "1 0.25\n2 0.75\n3 infinity\n";
+        assert_eq!(
+            compare("pr", expected, "1 0.250001\n2 0.75\n3 inf\n"),
+            Ok(())
+        );
+        assert_eq!(
+            compare("sssp", expected, "1 0.2501\n2 0.75\n3 infinity\n"),
+            Err("vertex 1 is 0.2501 instead of 0.25, 1 of 3 vertices differ".to_owned())
+        );
+        assert!(compare("sssp", expected, "1 0.25\n2 0.75\n3 1e308\n").is_err());
+        assert!(compare("lcc", "1 0\n", "1 1e-9\n").is_err());
+        assert!(compare("lcc", "1 0.5\n", "1 nan\n").is_err());
+    }
+
+    #[test]
+    fn labels_group_the_same_vertices() {
+        let expected = "1 1\n2 1\n3 3\n4 3\n";
+        // the labels are swapped, the components are the same
+        assert_eq!(compare("wcc", expected, "1 3\n2 3\n3 1\n4 1\n"), Ok(()));
+        assert_eq!(compare("cdlp", expected, "1 7\n2 7\n3 8\n4 8\n"), Ok(()));
+        // two components merged into one
+        assert!(compare("wcc", expected, "1 1\n2 1\n3 1\n4 1\n").is_err());
+        // a component split in two
+        assert!(compare("cdlp", expected, "1 1\n2 2\n3 3\n4 3\n").is_err());
+    }
+
+    #[test]
+    fn vertices_have_to_match() {
+        assert_eq!(
+            compare("bfs", "1 0\n2 1\n", "1 0\n3 1\n4 2\n"),
+            Err("1 vertices are missing from the output and 2 are not in the reference".to_owned())
+        );
+        assert_eq!(
+            compare("bfs", "1 0\n", "1\n"),
+            Err("invalid output: no value on line 1".to_owned())
+        );
+    }
+}
@@ -0,0 +1,155 @@
+use std::{collections::HashMap, process::Command};
+
+use anyhow::Result;
+use common::{config::Config, exit};
+use console::style;
+use diesel::{
+    dsl::{count_star, now},
+    ExpressionMethods, OptionalExtension, QueryDsl, SelectableHelper,
+};
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+use tracing::info;
+
+use crate::{
+    args::Cli,
+    model::{Campaign, NewCampaign},
+};
+
+use super::{
+    benchmark::{connect_db, platform_info},
+    validate::load_config,
+};
+
+/// Campaign the runs of a benchmark are grouped under. An explicit name continues the campaign of
+/// that name if it exists, otherwise a resumed benchmark continues the campaign of its journal
+pub(crate) async fn open_campaign(
+    conn: &mut AsyncPgConnection,
+    name: Option<String>,
+    resume_id: Option<i32>,
+    config: &Config,
+    config_file: &str,
+) -> Result<Campaign> {
+    use crate::schema::campaigns::dsl;
+
+    if name.is_none() {
+        if let Some(id) = resume_id {
+            return Ok(dsl::campaigns
+                .find(id)
+                .select(Campaign::as_select())
+                .first(conn)
+                .await?);
+        }
+    }
+
+    let name = name.unwrap_or(format!(
+        "campaign-{}",
+        chrono::Local::now().format("%Y%m%d-%H%M%S")
+    ));
+    if let Some(c) = dsl::campaigns
+        .filter(dsl::name.eq(&name))
+        .select(Campaign::as_select())
+        .first(conn)
+        .await
+        .optional()?
+    {
+        info!("continuing campaign {name} ({})", c.id);
+        return Ok(c);
+    }
+
+    let config_text = match std::fs::read_to_string(config_file) {
+        Ok(s) => s,
+        Err(err) => exit!(err, "Could not read {}", config_file),
+    };
+    let git_commit = git_commit();
+    Ok(diesel::insert_into(dsl::campaigns)
+        .values(NewCampaign {
+            name: &name,
+            labels: config.benchmark.labels.as_deref().unwrap_or_default(),
+            notes: config.benchmark.notes.as_deref(),
+            git_commit: git_commit.as_deref(),
+            config: &config_text,
+        })
+        .returning(Campaign::as_returning())
+        .get_result(conn)
+        .await?)
+}
+
+pub(crate) async fn finish_campaign(conn: &mut AsyncPgConnection, campaign_id: i32) -> Result<()> {
+    use crate::schema::campaigns::dsl::*;
+    diesel::update(campaigns.find(campaign_id))
+        .set(finished_at.eq(now))
+        .execute(conn)
+        .await?;
+    Ok(())
+}
+
+/// Commit of the repository the benchmarks are run from, if it is one
+fn git_commit() -> Option<String> {
+    let output = Command::new("git")
+        .args(["rev-parse", "HEAD"])
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    Some(String::from_utf8_lossy(&output.stdout).trim().to_owned())
+}
+
+pub async fn list(cli: &Cli) -> Result<()> {
+    use crate::schema::{benchmarks, campaigns};
+
+    let config = load_config(&cli.file)?;
+    let connect_args = platform_info(&config, cli.verbose).await?;
+    let mut connection = connect_db(&connect_args.master_ip).await?;
+
+    let list = campaigns::table
+        .order(campaigns::id)
+        .select(Campaign::as_select())
+        .load(&mut connection)
+        .await?;
+    let run_counts = benchmarks::table
+        .filter(benchmarks::campaign_id.is_not_null())
+        .group_by(benchmarks::campaign_id)
+        .select((benchmarks::campaign_id, count_star()))
+        .load::<(Option<i32>, i64)>(&mut connection)
+        .await?
+        .into_iter()
+        .filter_map(|(c, count)| c.map(|c| (c, count)))
+        .collect::<HashMap<i32, i64>>();
+
+    if list.is_empty() {
+        println!("No campaigns");
+        return Ok(());
+    }
+
+    let width = list.iter().map(|c| c.name.len()).max().unwrap_or(0).max(4);
+    println!(
+        "{}",
+        style(format!(
+            "{:>4}  {:<width$}  {:>5}  {:<19}  {:<19}  {:<8}  LABELS",
+            "ID", "NAME", "RUNS", "STARTED", "FINISHED", "COMMIT"
+        ))
+        .bold()
+    );
+    for c in list {
+        println!(
+            "{:>4}  {:<width$}  {:>5}  {:<19}  {:<19}  {:<8}  {}",
+            c.id,
+            c.name,
+            run_counts.get(&c.id).unwrap_or(&0),
+            c.started_at.format("%Y-%m-%d %H:%M:%S"),
+            c.finished_at
+                .map(|x| x.format("%Y-%m-%d %H:%M:%S").to_string())
+                .unwrap_or("-".to_owned()),
+            c.git_commit
+                .as_deref()
+                .map(|x| &x[..x.len().min(8)])
+                .unwrap_or("-"),
+            c.labels.join(", ")
+        );
+        if let Some(n) = c.notes {
+            println!("      {}", style(n).dim());
+        }
+    }
+    Ok(())
+}
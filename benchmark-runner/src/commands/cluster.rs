@@ -0,0 +1,236 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    env,
+    net::IpAddr,
+    path::Path,
+    process::Command,
+    sync::atomic::{AtomicBool, Ordering},
+    time::Duration,
+};
+
+use anyhow::{Context, Result};
+use common::{
+    command::{command_print, retry},
+    config::{Config, RetryStep},
+    exit,
+};
+use k8s_openapi::{api::core::v1::Pod, NamespaceResourceScope};
+use kube::{api::ListParams, Api, Client};
+use tokio::{net::TcpListener, time::sleep};
+use tracing::info;
+
+/// Kubeconfig of the k3s cluster installed on the nodes, downloaded by `setup`
+const K3S_KUBE_CONFIG: &str = "k3s/kube-config";
+/// Self contained copy of the context used on an existing cluster
+const EXISTING_KUBE_CONFIG: &str = "k3s/existing-kube-config";
+
+/// Services reached through node ports of the master on k3s, which are forwarded to the same
+/// local ports on an existing cluster: (pod label, local port, pod port)
+const FORWARDS: [(&str, u16, u16); 3] = [
+    ("app=graph-metrics", 30001, 9090),
+    ("app=postgres", 30002, 5432),
+    ("app=notifier", 30003, 8080),
+];
+
+static FORWARDING: AtomicBool = AtomicBool::new(false);
+
+/// Where the runner's own pods & images go, on the k3s cluster or an existing one
+#[derive(Debug, Clone)]
+pub(crate) struct Cluster {
+    pub existing: bool,
+    /// Registry prefix of the images, eg. `10.0.0.1:30000`
+    pub registry: String,
+    /// Namespace of the runner's & the drivers' resources
+    pub namespace: String,
+}
+
+impl Cluster {
+    pub fn new(config: &Config, master_ip: &IpAddr) -> Self {
+        let registry = config
+            .kubernetes
+            .as_ref()
+            .and_then(|x| x.registry.clone())
+            .filter(|_| config.existing_cluster());
+        Self {
+            existing: config.existing_cluster(),
+            registry: registry
+                .map(|x| x.trim_end_matches('/').to_owned())
+                .unwrap_or(format!("{master_ip}:30000")),
+            namespace: config.namespace().to_owned(),
+        }
+    }
+
+    /// Node selector of the runner's pods, the master node on k3s & any node otherwise
+    pub fn node_selector(&self) -> Option<BTreeMap<String, String>> {
+        (!self.existing)
+            .then(|| BTreeMap::from([("node-role.kubernetes.io/master".into(), "true".into())]))
+    }
+
+    /// Service type & node port of a runner service, which are port-forwarded on an existing cluster
+    pub fn node_port(&self, port: i32) -> (String, Option<i32>) {
+        if self.existing {
+            ("ClusterIP".into(), None)
+        } else {
+            ("NodePort".into(), Some(port))
+        }
+    }
+
+    /// Api of a kind of namespaced resources in the benchmark namespace
+    pub fn api<K>(&self, client: Client) -> Api<K>
+    where
+        K: kube::Resource<Scope = NamespaceResourceScope>,
+        <K as kube::Resource>::DynamicType: Default,
+    {
+        Api::namespaced(client, &self.namespace)
+    }
+}
+
+/// Point the kubernetes clients, and the kubectl, helm & ansible commands that are run, at the
+/// cluster. For an existing cluster the context is flattened into a kubeconfig of its own
+pub(crate) fn use_kube_config(config: &Config) -> Result<()> {
+    let kube = config.kubernetes.as_ref();
+    let Some(kubeconfig) = kube.and_then(|x| x.kubeconfig.as_ref()) else {
+        env::set_var("KUBECONFIG", K3S_KUBE_CONFIG);
+        return Ok(());
+    };
+
+    let mut args = vec![
+        "--kubeconfig",
+        kubeconfig,
+        "config",
+        "view",
+        "--raw",
+        "--flatten",
+        "--minify",
+    ];
+    if let Some(context) = kube.and_then(|x| x.context.as_ref()) {
+        args.extend(["--context", context]);
+    }
+    let output = match Command::new("kubectl").args(&args).output() {
+        Ok(o) => o,
+        Err(err) => exit!(err, "Could not run kubectl, is it installed?"),
+    };
+    if !output.status.success() {
+        exit!(
+            String::from_utf8_lossy(&output.stderr).to_string(),
+            "Could not read kubeconfig {}",
+            kubeconfig
+        );
+    }
+    std::fs::write(EXISTING_KUBE_CONFIG, output.stdout)?;
+
+    // commands run from other directories, so the path has to be absolute
+    let path = Path::new(EXISTING_KUBE_CONFIG).canonicalize()?;
+    env::set_var("KUBECONFIG", path);
+    Ok(())
+}
+
+/// Forward the local ports of the postgres, metrics & notifier services to their pods on an
+/// existing cluster, for the lifetime of the runner. The pods are looked up on every connection,
+/// as they are recreated during a benchmark
+pub(crate) async fn forward_services(namespace: &str) -> Result<()> {
+    if FORWARDING.swap(true, Ordering::SeqCst) {
+        return Ok(());
+    }
+
+    let pods: Api<Pod> = Api::namespaced(Client::try_default().await?, namespace);
+    for (label, local_port, port) in FORWARDS {
+        let listener = match TcpListener::bind(("127.0.0.1", local_port)).await {
+            Ok(l) => l,
+            Err(err) => exit!(err, "Could not listen on local port {}", local_port),
+        };
+        let pods = pods.clone();
+        tokio::spawn(async move {
+            while let Ok((mut conn, _)) = listener.accept().await {
+                let pods = pods.clone();
+                tokio::spawn(async move {
+                    if let Err(err) = forward(&pods, label, port, &mut conn).await {
+                        info!("port-forward to {label}:{port} closed: {err}");
+                    }
+                });
+            }
+        });
+    }
+    Ok(())
+}
+
+async fn forward(
+    pods: &Api<Pod>,
+    label: &str,
+    port: u16,
+    conn: &mut tokio::net::TcpStream,
+) -> Result<()> {
+    let pod = running_pod(pods, label).await?;
+    let mut forwarder = pods.portforward(&pod, &[port]).await?;
+    let mut upstream = forwarder
+        .take_stream(port)
+        .context("port not found in forwarder")?;
+    tokio::io::copy_bidirectional(conn, &mut upstream).await?;
+    drop(upstream);
+    forwarder.join().await?;
+    Ok(())
+}
+
+/// Name of a running pod with the label, waiting up to two minutes for one to start
+pub(crate) async fn running_pod(pods: &Api<Pod>, label: &str) -> Result<String> {
+    for _ in 0..120 {
+        let list = pods.list(&ListParams::default().labels(label)).await?;
+        let running = list.items.into_iter().find(|x| {
+            x.status
+                .as_ref()
+                .and_then(|s| s.phase.as_deref())
+                .is_some_and(|p| p == "Running")
+        });
+        if let Some(name) = running.and_then(|x| x.metadata.name) {
+            return Ok(name);
+        }
+        sleep(Duration::from_secs(1)).await;
+    }
+    exit!("", "No running pod with label {}", label)
+}
+
+/// Copy the datasets into the NFS server pod of an existing cluster, which has no master node to
+/// rsync them to
+pub(crate) async fn copy_datasets_to_pod(
+    cluster: &Cluster,
+    datasets: &[String],
+    verbose: bool,
+) -> Result<()> {
+    let pods: Api<Pod> = cluster.api(Client::try_default().await?);
+    let nfs_pod = running_pod(&pods, "app=nfs-server").await?;
+    for dataset in datasets {
+        let mut files = tokio::fs::read_dir(format!("datasets/{dataset}")).await?;
+        while let Some(file) = files.next_entry().await? {
+            if !file.file_type().await?.is_file() {
+                continue;
+            }
+            let name = file.file_name().to_string_lossy().to_string();
+            let (source, destination) = (
+                format!("datasets/{dataset}/{name}"),
+                format!("{nfs_pod}:/exports/bench-storage/{name}"),
+            );
+            let args = ["cp", "-n", &cluster.namespace, &source, &destination];
+            let msgs = [
+                format!("Copying {name} of dataset {dataset}"),
+                format!("Could not copy {name} of dataset {dataset}"),
+                format!("Copied {name} of dataset {dataset}"),
+            ];
+            retry(
+                RetryStep::Datasets,
+                &format!("copy {name} of {dataset}"),
+                || {
+                    command_print(
+                        "kubectl",
+                        &args,
+                        verbose,
+                        msgs.each_ref().map(|x| x.as_str()),
+                        ".",
+                        HashMap::<&str, &str>::new(),
+                    )
+                },
+            )
+            .await?;
+        }
+    }
+    Ok(())
+}
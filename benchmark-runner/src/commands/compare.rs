@@ -0,0 +1,207 @@
+use anyhow::Result;
+use common::exit;
+use console::style;
+
+use crate::{
+    args::{Cli, CompareArgs},
+    results::{fetch_run_records, RunRecord},
+    stats::{group_cells, mann_whitney, min_p_value, Stats},
+};
+
+use super::{
+    benchmark::{connect_db, platform_info},
+    summary::pad_columns,
+    validate::load_config,
+};
+
+const HEADER: [&str; 11] = [
+    "driver",
+    "dataset",
+    "algorithm",
+    "nodes",
+    "baseline",
+    "candidate",
+    "change",
+    "p",
+    "cpu",
+    "ram",
+    "result",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Verdict {
+    Regressed,
+    Improved,
+    Unchanged,
+}
+
+/// Compare the cells both campaigns ran, the median runtime of the candidate relative to the
+/// baseline is tested for significance with a Mann-Whitney U test over the repeats
+pub async fn compare(args: &CompareArgs, cli: &Cli) -> Result<()> {
+    let config = load_config(&cli.file)?;
+    let connect_args = platform_info(&config, cli.verbose).await?;
+    let mut connection = connect_db(&connect_args.master_ip).await?;
+
+    let baseline = fetch_run_records(&mut connection, None, Some(args.baseline.clone())).await?;
+    if baseline.is_empty() {
+        exit!("", "No benchmark results for campaign {}", args.baseline);
+    }
+    let candidate = fetch_run_records(&mut connection, None, Some(args.candidate.clone())).await?;
+    if candidate.is_empty() {
+        exit!("", "No benchmark results for campaign {}", args.candidate);
+    }
+
+    let baseline = group_cells(&baseline);
+    let mut candidate = group_cells(&candidate);
+
+    let mut rows = Vec::new();
+    let mut verdicts = Vec::new();
+    let mut only_baseline = 0;
+    let mut untestable = 0;
+    for (key, a) in &baseline {
+        let b = match candidate.remove(key) {
+            Some(b) => b,
+            None => {
+                only_baseline += 1;
+                continue;
+            }
+        };
+
+        let runtime =
+            |runs: &[&RunRecord]| runs.iter().map(|x| x.runtime_ms as f64).collect::<Vec<_>>();
+        let (ra, rb) = (runtime(a), runtime(&b));
+        let (sa, sb) = match (Stats::new(&ra), Stats::new(&rb)) {
+            (Some(sa), Some(sb)) => (sa, sb),
+            _ => continue,
+        };
+
+        let change = relative_change(sa.median, sb.median);
+        let p = mann_whitney(&ra, &rb).unwrap_or(1.0);
+        // with too few repeats the test can't be significant, the threshold alone decides
+        let testable = min_p_value(sa.n, sb.n) <= args.alpha;
+        if !testable {
+            untestable += 1;
+        }
+        let significant = !testable || p <= args.alpha;
+        let verdict = match change {
+            Some(c) if significant && c > args.threshold => Verdict::Regressed,
+            Some(c) if significant && c < -args.threshold => Verdict::Improved,
+            _ => Verdict::Unchanged,
+        };
+
+        let cpu = relative_change(
+            mean(a.iter().filter_map(|x| x.cpu_avg)),
+            mean(b.iter().filter_map(|x| x.cpu_avg)),
+        );
+        let ram = relative_change(
+            mean(a.iter().filter_map(|x| x.ram_avg)),
+            mean(b.iter().filter_map(|x| x.ram_avg)),
+        );
+
+        rows.push([
+            key.driver.clone(),
+            key.dataset.clone(),
+            key.algorithm.clone(),
+            key.nodes.to_string(),
+            format!("{:.1}", sa.median),
+            format!("{:.1}", sb.median),
+            percent(change),
+            if testable {
+                format!("{p:.3}")
+            } else {
+                format!("{p:.3}*")
+            },
+            percent(cpu),
+            percent(ram),
+            match verdict {
+                Verdict::Regressed => "regressed",
+                Verdict::Improved => "improved",
+                Verdict::Unchanged => "-",
+            }
+            .to_owned(),
+        ]);
+        verdicts.push(verdict);
+    }
+
+    if rows.is_empty() {
+        exit!(
+            "",
+            "Campaigns {} and {} have no benchmark cells in common",
+            args.baseline,
+            args.candidate
+        );
+    }
+
+    let (header, rows) = pad_columns(&HEADER, &rows, 3);
+    println!("{}", style(header.join("  ")).bold());
+    for (mut cells, verdict) in rows.into_iter().zip(&verdicts) {
+        match verdict {
+            Verdict::Regressed => cells[10] = style(&cells[10]).red().to_string(),
+            Verdict::Improved => cells[10] = style(&cells[10]).green().to_string(),
+            Verdict::Unchanged => {}
+        }
+        println!("{}", cells.join("  "));
+    }
+    println!(
+        "{}",
+        style("median runtimes in ms, changes relative to the baseline").dim()
+    );
+    if only_baseline > 0 || !candidate.is_empty() {
+        println!(
+            "{}",
+            style(format!(
+                "{only_baseline} cells only in {}, {} cells only in {}",
+                args.baseline,
+                candidate.len(),
+                args.candidate
+            ))
+            .dim()
+        );
+    }
+    if untestable > 0 {
+        println!(
+            "{}",
+            style(format!(
+                "* {untestable} cells have too few repeats to be significant at {}, \
+                 their result only compares the change with the threshold",
+                args.alpha
+            ))
+            .yellow()
+        );
+    }
+
+    let regressed = verdicts
+        .iter()
+        .filter(|x| **x == Verdict::Regressed)
+        .count();
+    if regressed > 0 {
+        exit!(
+            format!("{regressed} regressions"),
+            "{} cells of {} regressed by more than {}% against {}",
+            regressed,
+            args.candidate,
+            args.threshold,
+            args.baseline
+        );
+    }
+    Ok(())
+}
+
+fn mean(values: impl Iterator<Item = f64>) -> Option<f64> {
+    let values = values.collect::<Vec<_>>();
+    Stats::new(&values).map(|x| x.mean)
+}
+
+/// Change from `a` to `b` in percent of `a`
+fn relative_change(a: impl Into<Option<f64>>, b: impl Into<Option<f64>>) -> Option<f64> {
+    match (a.into(), b.into()) {
+        (Some(a), Some(b)) if a != 0.0 => Some((b - a) / a * 100.0),
+        _ => None,
+    }
+}
+
+fn percent(change: Option<f64>) -> String {
+    change
+        .map(|x| format!("{x:+.1}%"))
+        .unwrap_or("-".to_owned())
+}
@@ -0,0 +1,257 @@
+use std::{fs::File, path::Path, sync::Arc, time::Instant};
+
+use anyhow::Result;
+use arrow_array::{
+    ArrayRef, BooleanArray, Float64Array, Int32Array, Int64Array, RecordBatch, StringArray,
+};
+use arrow_schema::{DataType, Field, Schema};
+use common::{
+    command::{finish_progress, progress},
+    exit,
+};
+use parquet::arrow::ArrowWriter;
+
+use crate::{
+    args::{Cli, ExportArgs, ExportFormat},
+    commands::{
+        benchmark::{connect_db, platform_info},
+        validate::load_config,
+    },
+    results::{fetch_run_records, RunRecord},
+};
+
+pub async fn export(args: &ExportArgs, cli: &Cli) -> Result<()> {
+    let config = load_config(&cli.file)?;
+    let connect_args = platform_info(&config, cli.verbose).await?;
+    let mut connection = connect_db(&connect_args.master_ip).await?;
+
+    let pb = progress("Exporting benchmark results");
+    let start = Instant::now();
+    let records =
+        fetch_run_records(&mut connection, args.runs.clone(), args.campaign.clone()).await?;
+    if records.is_empty() {
+        pb.finish_and_clear();
+        exit!("", "No benchmark results to export");
+    }
+
+    let output = args.output.clone().unwrap_or(format!(
+        "results/results.{}",
+        match args.format {
+            ExportFormat::Csv => "csv",
+            ExportFormat::Json => "jsonl",
+            ExportFormat::Parquet => "parquet",
+        }
+    ));
+    if let Some(parent) = Path::new(&output).parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    match args.format {
+        ExportFormat::Csv => write_csv(&records, &output)?,
+        ExportFormat::Json => write_json(&records, &output)?,
+        ExportFormat::Parquet => write_parquet(&records, &output)?,
+    }
+
+    finish_progress(
+        &format!("Exported {} runs", records.len()),
+        &output,
+        start.elapsed(),
+        Some(pb),
+    );
+    Ok(())
+}
+
+fn write_csv(records: &[RunRecord], output: &str) -> Result<()> {
+    let mut writer = csv::Writer::from_path(output)?;
+    for record in records {
+        writer.serialize(record)?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+fn write_json(records: &[RunRecord], output: &str) -> Result<()> {
+    let lines = records
+        .iter()
+        .map(serde_json::to_string)
+        .collect::<Result<Vec<String>, _>>()?;
+    std::fs::write(output, lines.join("\n") + "\n")?;
+    Ok(())
+}
+
+fn write_parquet(records: &[RunRecord], output: &str) -> Result<()> {
+    let schema = Arc::new(Schema::new(vec![
+        Field::new("run_id", DataType::Int32, false),
+        Field::new("campaign", DataType::Utf8, true),
+        Field::new("driver", DataType::Utf8, true),
+        Field::new("dataset", DataType::Utf8, false),
+        Field::new("algorithm", DataType::Utf8, false),
+        Field::new("nodes", DataType::Int32, false),
+        Field::new("repeat", DataType::Int64, false),
+        Field::new("vertices", DataType::Int32, true),
+        Field::new("edges", DataType::Int32, true),
+        Field::new("load_ms", DataType::Int32, true),
+        Field::new("runtime_ms", DataType::Int32, false),
+        Field::new("makespan_ms", DataType::Int32, false),
+        Field::new("cpu_avg", DataType::Float64, true),
+        Field::new("cpu_max", DataType::Float64, true),
+        Field::new("ram_avg", DataType::Float64, true),
+        Field::new("ram_max", DataType::Float64, true),
+        Field::new("status", DataType::Utf8, false),
+        Field::new("tainted", DataType::Boolean, false),
+        Field::new("validation", DataType::Utf8, true),
+        Field::new("started_at", DataType::Utf8, true),
+        Field::new("finished_at", DataType::Utf8, true),
+        Field::new("config_hash", DataType::Utf8, true),
+        Field::new("speedup", DataType::Float64, true),
+        Field::new("efficiency", DataType::Float64, true),
+        Field::new("karp_flatt", DataType::Float64, true),
+    ]));
+
+    let columns: Vec<ArrayRef> = vec![
+        Arc::new(Int32Array::from_iter_values(
+            records.iter().map(|x| x.run_id),
+        )),
+        Arc::new(StringArray::from_iter(
+            records.iter().map(|x| x.campaign.as_deref()),
+        )),
+        Arc::new(StringArray::from_iter(
+            records.iter().map(|x| x.driver.as_deref()),
+        )),
+        Arc::new(StringArray::from_iter_values(
+            records.iter().map(|x| &x.dataset),
+        )),
+        Arc::new(StringArray::from_iter_values(
+            records.iter().map(|x| &x.algorithm),
+        )),
+        Arc::new(Int32Array::from_iter_values(
+            records.iter().map(|x| x.nodes),
+        )),
+        Arc::new(Int64Array::from_iter_values(
+            records.iter().map(|x| x.repeat),
+        )),
+        Arc::new(Int32Array::from_iter(records.iter().map(|x| x.vertices))),
+        Arc::new(Int32Array::from_iter(records.iter().map(|x| x.edges))),
+        Arc::new(Int32Array::from_iter(records.iter().map(|x| x.load_ms))),
+        Arc::new(Int32Array::from_iter_values(
+            records.iter().map(|x| x.runtime_ms),
+        )),
+        Arc::new(Int32Array::from_iter_values(
+            records.iter().map(|x| x.makespan_ms),
+        )),
+        Arc::new(Float64Array::from_iter(records.iter().map(|x| x.cpu_avg))),
+        Arc::new(Float64Array::from_iter(records.iter().map(|x| x.cpu_max))),
+        Arc::new(Float64Array::from_iter(records.iter().map(|x| x.ram_avg))),
+        Arc::new(Float64Array::from_iter(records.iter().map(|x| x.ram_max))),
+        Arc::new(StringArray::from_iter_values(
+            records.iter().map(|x| &x.status),
+        )),
+        Arc::new(BooleanArray::from(
+            records.iter().map(|x| x.tainted).collect::<Vec<_>>(),
+        )),
+        Arc::new(StringArray::from_iter(
+            records.iter().map(|x| x.validation.as_deref()),
+        )),
+        Arc::new(StringArray::from_iter(
+            records.iter().map(|x| x.started_at.as_deref()),
+        )),
+        Arc::new(StringArray::from_iter(
+            records.iter().map(|x| x.finished_at.as_deref()),
+        )),
+        Arc::new(StringArray::from_iter(
+            records.iter().map(|x| x.config_hash.as_deref()),
+        )),
+        Arc::new(Float64Array::from_iter(records.iter().map(|x| x.speedup))),
+        Arc::new(Float64Array::from_iter(
+            records.iter().map(|x| x.efficiency),
+        )),
+        Arc::new(Float64Array::from_iter(
+            records.iter().map(|x| x.karp_flatt),
+        )),
+    ];
+
+    let batch = RecordBatch::try_new(schema.clone(), columns)?;
+    let mut writer = ArrowWriter::try_new(File::create(output)?, schema, None)?;
+    writer.write(&batch)?;
+    writer.close()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+
+    use super::*;
+    use crate::results::read_run_records;
+
+    fn records() -> Vec<RunRecord> {
+        let first = RunRecord {
+            run_id: 7,
+            campaign: Some("baseline".to_owned()),
+            driver: Some("native".to_owned()),
+            dataset: "tiny".to_owned(),
+            algorithm: "pr".to_owned(),
+            nodes: 2,
+            repeat: 0,
+            vertices: Some(10),
+            edges: Some(17),
+            load_ms: Some(40),
+            runtime_ms: 12,
+            makespan_ms: 52,
+            cpu_avg: Some(0.5),
+            cpu_max: Some(1.25),
+            ram_avg: None,
+            ram_max: None,
+            status: "completed".to_owned(),
+            tainted: true,
+            validation: Some("passed".to_owned()),
+            started_at: Some("2024-07-01T09:00:00.000".to_owned()),
+            finished_at: Some("2024-07-01T09:00:01.000".to_owned()),
+            config_hash: Some("abc".to_owned()),
+            speedup: Some(1.0),
+            efficiency: Some(1.0),
+            karp_flatt: None,
+        };
+        let second = RunRecord {
+            repeat: 1,
+            load_ms: None,
+            runtime_ms: 11,
+            makespan_ms: 11,
+            campaign: None,
+            validation: None,
+            tainted: false,
+            ..first.clone()
+        };
+        vec![first, second]
+    }
+
+    fn round_trip(write: fn(&[RunRecord], &str) -> Result<()>, extension: &str) {
+        let file = env::temp_dir().join(format!(
+            "benchmark-runner-{}-export.{extension}",
+            std::process::id()
+        ));
+        let file = file.to_str().unwrap();
+        let records = records();
+        write(&records, file).unwrap();
+        let read = read_run_records(file).unwrap();
+        std::fs::remove_file(file).unwrap();
+
+        assert_eq!(read.len(), records.len());
+        for (read, written) in read.iter().zip(&records) {
+            assert_eq!(
+                serde_json::to_value(read).unwrap(),
+                serde_json::to_value(written).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn csv_round_trip() {
+        round_trip(write_csv, "csv");
+    }
+
+    #[test]
+    fn jsonl_round_trip() {
+        round_trip(write_json, "jsonl");
+    }
+}
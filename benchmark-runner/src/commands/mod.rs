@@ -1,5 +1,13 @@
 pub mod benchmark;
+pub mod campaigns;
+pub mod cluster;
+pub mod compare;
 pub mod destroy;
+pub mod export;
 pub mod ls;
+pub mod plan;
 pub mod port_forwards;
+pub mod report;
 pub mod setup;
+pub mod summary;
+pub mod validate;
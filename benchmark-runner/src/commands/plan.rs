@@ -0,0 +1,109 @@
+use anyhow::Result;
+use common::exit;
+use console::style;
+
+use crate::args::Cli;
+
+use super::{
+    benchmark::{driver_algorithms, load_datasets},
+    validate::load_config,
+};
+
+/// Print the ordered list of steps `benchmark` would perform, without running any benchmarks. Nothing
+/// is written or sent to the cluster, the drivers only render their manifests when installed
+pub async fn plan(cli: &Cli) -> Result<()> {
+    let mut config = load_config(&cli.file)?;
+    let datasets = load_datasets(&config).await?;
+
+    let mut drivers = Vec::new();
+    for driver in &config.benchmark.drivers {
+        match base_driver::get_driver_config(driver) {
+            Some(d) => drivers.push(d),
+            None => exit!("", "Could not find driver {}", driver),
+        }
+    }
+
+    config.setup.node_configs.sort_by(|a, b| b.cmp(a));
+
+    let mut step = 0;
+    let mut next_step = || {
+        step += 1;
+        style(format!("[{step:>3}]")).dim()
+    };
+
+    let mut jobs = 0;
+    for n_nodes in &config.setup.node_configs {
+        println!(
+            "{} {} {}",
+            next_step(),
+            if config.existing_cluster() {
+                "Scale drivers to"
+            } else {
+                "Resize cluster to"
+            },
+            style(format!("{} nodes", config.cluster_size(*n_nodes))).bold()
+        );
+        if config.parallel_drivers() {
+            println!(
+                "{} Split the nodes into pools of {} for every driver, benchmarked in parallel",
+                next_step(),
+                style(format!("{n_nodes} nodes")).bold()
+            );
+        }
+        for driver in &drivers {
+            let name = driver.name();
+            let options = config
+                .setup
+                .graph_platform_args
+                .as_ref()
+                .and_then(|x| x.get(&name));
+            let mut extra_vars = driver.extra_vars(*n_nodes, options)?;
+            extra_vars.push(format!("namespace={}", config.namespace()));
+            println!(
+                "{}   Install {} (--extra-vars {})",
+                next_step(),
+                style(&name).cyan(),
+                extra_vars.join(" ")
+            );
+
+            for dataset in &datasets {
+                for repeat in 0..config.benchmark.repeat {
+                    let mut flags = Vec::new();
+                    if repeat == 0 {
+                        flags.push("load data");
+                    }
+                    if repeat == config.benchmark.repeat - 1 {
+                        flags.push("drop data");
+                    }
+                    println!(
+                        "{}     Run [{}] on {} (repeat {}/{}{}{})",
+                        next_step(),
+                        driver_algorithms(&name, &dataset.algorithms).join(", "),
+                        style(&dataset.name).green(),
+                        repeat + 1,
+                        config.benchmark.repeat,
+                        if flags.is_empty() { "" } else { ", " },
+                        flags.join(", ")
+                    );
+                    jobs += 1;
+                }
+            }
+            println!("{}   Remove {}", next_step(), style(&name).cyan());
+        }
+    }
+
+    println!(
+        "\n{} cluster sizes, {} drivers, {} datasets, {} benchmark jobs",
+        config.setup.node_configs.len(),
+        drivers.len(),
+        datasets.len(),
+        jobs
+    );
+    for dataset in &datasets {
+        let skipped = dataset.config.skip_algos.clone().unwrap_or_default();
+        if !skipped.is_empty() {
+            println!("{} skips algorithms: {}", dataset.name, skipped.join(", "));
+        }
+    }
+    Ok(())
+}
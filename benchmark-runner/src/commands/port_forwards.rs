@@ -1,5 +1,3 @@
-use std::env;
-
 use anyhow::{Context, Result};
 use common::exit;
 use futures_util::{StreamExt, TryStreamExt};
@@ -17,16 +15,19 @@ use tracing::info;
 
 use crate::args::Cli;
 
-pub async fn dashboard(_: &Cli) -> Result<()> {
-    env::set_var("KUBECONFIG", "k3s/kube-config");
+use super::{cluster::use_kube_config, validate::load_config};
+
+pub async fn dashboard(cli: &Cli) -> Result<()> {
+    let config = load_config(&cli.file)?;
+    use_kube_config(&config)?;
     let client = Client::try_default().await?;
-    let pods: Api<Pod> = Api::default_namespaced(client.clone());
+    let pods: Api<Pod> = Api::namespaced(client.clone(), config.namespace());
     let p = pods.list(&ListParams::default().labels("app.kubernetes.io/name=kubernetes-dashboard,app.kubernetes.io/instance=kubernetes-dashboard")).await?;
     if p.items.is_empty() {
         exit!("", "Could not find dashboard in cluster");
     }
 
-    let token: Api<ServiceAccount> = Api::default_namespaced(client);
+    let token: Api<ServiceAccount> = Api::namespaced(client, config.namespace());
     let token = token
         .create_token_request(
             "admin-user",
@@ -47,10 +48,11 @@ pub async fn dashboard(_: &Cli) -> Result<()> {
     Ok(())
 }
 
-pub async fn postgres(_: &Cli) -> Result<()> {
-    env::set_var("KUBECONFIG", "k3s/kube-config");
+pub async fn postgres(cli: &Cli) -> Result<()> {
+    let config = load_config(&cli.file)?;
+    use_kube_config(&config)?;
     let client = Client::try_default().await?;
-    let pods: Api<Pod> = Api::default_namespaced(client.clone());
+    let pods: Api<Pod> = Api::namespaced(client.clone(), config.namespace());
     let p = pods
         .list(&ListParams::default().labels("app=postgres"))
         .await?;
@@ -0,0 +1,46 @@
+use std::{path::Path, time::Instant};
+
+use anyhow::Result;
+use common::{
+    command::{finish_progress, progress},
+    exit,
+};
+
+use crate::{
+    args::ReportArgs,
+    report::render_charts,
+    results::read_run_records,
+    stats::{scaling, summarize},
+};
+
+use super::summary::write_report;
+
+/// Render the report of exported results, without connecting to the cluster
+pub async fn report(args: &ReportArgs) -> Result<()> {
+    let mut records = read_run_records(&args.input)?;
+    if let Some(campaign) = &args.campaign {
+        records.retain(|x| x.campaign.as_ref() == Some(campaign));
+    }
+    if records.is_empty() {
+        exit!("", "No benchmark results to report in {}", args.input);
+    }
+
+    let pb = progress("Rendering report");
+    let start = Instant::now();
+    let dir = Path::new(&args.output);
+    let charts = render_charts(&records, dir)?;
+    write_report(
+        &summarize(&records),
+        &scaling(&records),
+        args.campaign.as_deref(),
+        &dir.join("index.html").to_string_lossy(),
+        &charts,
+    )?;
+    finish_progress(
+        &format!("Rendered {} charts", charts.len()),
+        &args.output,
+        start.elapsed(),
+        Some(pb),
+    );
+    Ok(())
+}
@@ -1,20 +1,29 @@
 use std::{
     collections::HashMap,
+    net::{IpAddr, Ipv4Addr},
     path::{Path, PathBuf},
     time::Instant,
 };
 
 use anyhow::{bail, Result};
 use common::{
-    command::{command_no_print, command_print, finish_progress, progress},
-    config::{parse_config, KubeSetup, PlatformConnectInfo, SetupArgs},
+    command::{
+        command_no_print, command_print, finish_progress, progress, retry, set_retry_policy,
+    },
+    config::{Config, KubeSetup, PlatformConnectInfo, RetryStep, SetupArgs},
     exit,
 };
 use serde::{Deserialize, Serialize};
 use tokio::fs::{self, remove_file};
 use tracing::info;
 
-use crate::args::{self, Cli};
+use crate::{
+    args::{self, Cli},
+    commands::{
+        cluster::{use_kube_config, Cluster},
+        validate::load_config,
+    },
+};
 
 struct ImageConfig<'a> {
     name: &'a str,
@@ -26,10 +35,6 @@ const STANDARD_IMAGES: &[ImageConfig] = &[
         name: "metrics",
         path: "../metrics",
     },
-    ImageConfig {
-        name: "graphs",
-        path: "../graphs",
-    },
     ImageConfig {
         name: "notifier",
         path: "../notifier",
@@ -109,6 +114,9 @@ async fn setup_master_node(
         if k.dashboard.is_some() && *k.dashboard.as_ref().unwrap() {
             vars.insert("dashboard", "1".to_owned());
         }
+        if let Some(namespace) = &k.namespace {
+            vars.insert("namespace", namespace.clone());
+        }
     }
 
     let master_hosts = HashMap::from([("master", Item { hosts, vars })]);
@@ -143,24 +151,27 @@ async fn setup_master_node(
     if verbose {
         env.insert("DEBUG_ANSIBLE", "1");
     }
-    command_print(
-        "ansible-playbook",
-        &[
-            "main-master.yaml",
-            "--private-key",
-            &connect_args.private_key_file,
-            "-i",
-            "inventory/master-hosts.yaml",
-        ],
-        verbose,
-        [
-            "Setting up master node",
-            "Could not setup master node",
-            "Master node ready",
-        ],
-        "k3s",
-        env.clone(),
-    )
+    let args = [
+        "main-master.yaml",
+        "--private-key",
+        &connect_args.private_key_file,
+        "-i",
+        "inventory/master-hosts.yaml",
+    ];
+    retry(RetryStep::Ansible, "set up master node", || {
+        command_print(
+            "ansible-playbook",
+            &args,
+            verbose,
+            [
+                "Setting up master node",
+                "Could not setup master node",
+                "Master node ready",
+            ],
+            "k3s",
+            env.clone(),
+        )
+    })
     .await?;
 
     let kube_config = fs::read_to_string("k3s/kube-config").await?;
@@ -173,22 +184,23 @@ async fn setup_master_node(
     let pb = progress("Building & loading internal benchmark resources");
     let start = Instant::now();
     for image in STANDARD_IMAGES {
-        command_no_print(
-            "ansible-playbook",
-            &[
-                "load-image.yaml",
-                "--private-key",
-                &connect_args.private_key_file,
-                "-i",
-                "inventory/master-hosts.yaml",
-                "--extra-vars",
-                &format!(
-                    "image_path={} image_name={} repo=system",
-                    image.path, image.name
-                ),
-            ],
-            "k3s",
-            env.clone(),
+        let extra_vars = format!(
+            "image_path={} image_name={} repo=system",
+            image.path, image.name
+        );
+        let args = [
+            "load-image.yaml",
+            "--private-key",
+            &connect_args.private_key_file,
+            "-i",
+            "inventory/master-hosts.yaml",
+            "--extra-vars",
+            &extra_vars,
+        ];
+        retry(
+            RetryStep::Images,
+            &format!("load {} image", image.name),
+            || command_no_print("ansible-playbook", &args, "k3s", env.clone()),
         )
         .await?;
     }
@@ -200,26 +212,31 @@ async fn setup_master_node(
     );
 
     for driver in drivers {
-        command_print(
-            "ansible-playbook",
-            &[
-                "load-image.yaml",
-                "--private-key",
-                &connect_args.private_key_file,
-                "-i",
-                "inventory/master-hosts.yaml",
-                "--extra-vars",
-                &format!("image_path=../drivers/{driver} image_name={driver} repo=benches"),
-            ],
-            verbose,
-            [
-                &format!("Building & loading {driver} driver into kubernetes"),
-                &format!("Could not build / load {driver} driver into kubernetes"),
-                &format!("Done building & loading {driver} driver into kubernetes"),
-            ],
-            "k3s",
-            env.clone(),
-        )
+        let extra_vars = format!("image_path=../drivers/{driver} image_name={driver} repo=benches");
+        let args = [
+            "load-image.yaml",
+            "--private-key",
+            &connect_args.private_key_file,
+            "-i",
+            "inventory/master-hosts.yaml",
+            "--extra-vars",
+            &extra_vars,
+        ];
+        let msgs = [
+            format!("Building & loading {driver} driver into kubernetes"),
+            format!("Could not build / load {driver} driver into kubernetes"),
+            format!("Done building & loading {driver} driver into kubernetes"),
+        ];
+        retry(RetryStep::Images, &format!("load {driver} image"), || {
+            command_print(
+                "ansible-playbook",
+                &args,
+                verbose,
+                msgs.each_ref().map(|x| x.as_str()),
+                "k3s",
+                env.clone(),
+            )
+        })
         .await?;
     }
     Ok(())
@@ -265,25 +282,213 @@ async fn setup_worker_node(connect_args: &PlatformConnectInfo, verbose: bool) ->
         env.insert("DEBUG_ANSIBLE", "1");
     }
 
+    let args = [
+        "worker.yaml",
+        "--private-key",
+        &connect_args.private_key_file,
+        "-i",
+        "inventory/worker-hosts.yaml",
+    ];
+    retry(RetryStep::Ansible, "set up worker nodes", || {
+        command_print(
+            "ansible-playbook",
+            &args,
+            verbose,
+            [
+                "Setting up worker nodes",
+                "Could not setup worker nodes",
+                "Worker nodes ready",
+            ],
+            "k3s",
+            env.clone(),
+        )
+    })
+    .await
+}
+
+/// Manifests applied by the master playbook, that are applied to an existing cluster instead
+const EXISTING_CLUSTER_MANIFESTS: &[&str] = &[
+    "data/admin-user.yaml",
+    "data/cluster-role-binding.yaml",
+    "data/nfs.yaml",
+    "data/common-pv.yaml",
+    "data/postgres.yaml",
+];
+
+/// Set up the benchmark services on an existing cluster instead of installing k3s: the manifests
+/// are applied with kubectl, and the images are pushed to the configured registry
+async fn setup_existing_cluster(config: &Config, verbose: bool) -> Result<()> {
+    use_kube_config(config)?;
+    let registry = Cluster::new(config, &IpAddr::V4(Ipv4Addr::LOCALHOST)).registry;
+    let namespace = config.namespace();
+
+    // the playbooks of the drivers run locally, and reach the cluster through KUBECONFIG
+    let hosts = HashMap::from([("localhost".to_owned(), ())]);
+    let vars = HashMap::from([
+        ("ansible_connection", "local".to_owned()),
+        ("ansible_python_interpreter", "python3".to_owned()),
+        ("namespace", namespace.to_owned()),
+    ]);
+    fs::write(
+        "k3s/inventory/master-hosts.yaml",
+        serde_yaml::to_string(&HashMap::from([("master", Item { hosts, vars })]))?,
+    )
+    .await?;
+
+    let mut manifests = vec![format!(
+        "apiVersion: v1\nkind: Namespace\nmetadata:\n  name: {namespace}\n"
+    )];
+    for file in EXISTING_CLUSTER_MANIFESTS {
+        let text = fs::read_to_string(Path::new("k3s").join(file)).await?;
+        for doc in serde_yaml::Deserializer::from_str(&text) {
+            let mut doc = serde_yaml::Value::deserialize(doc)?;
+            if !doc.is_null() {
+                unpin_from_master(&mut doc);
+                set_namespace(&mut doc, namespace);
+                manifests.push(serde_yaml::to_string(&doc)?);
+            }
+        }
+    }
+    fs::write("k3s/existing-manifests.yaml", manifests.join("---\n")).await?;
     command_print(
-        "ansible-playbook",
+        "kubectl",
+        &["apply", "-f", "existing-manifests.yaml"],
+        verbose,
+        [
+            "Creating benchmark services",
+            "Could not create benchmark services",
+            "Benchmark services created",
+        ],
+        "k3s",
+        HashMap::<&str, &str>::new(),
+    )
+    .await?;
+    command_print(
+        "kubectl",
         &[
-            "worker.yaml",
-            "--private-key",
-            &connect_args.private_key_file,
-            "-i",
-            "inventory/worker-hosts.yaml",
+            "rollout",
+            "status",
+            "-n",
+            namespace,
+            "deployment/nfs-server",
+            "--timeout=600s",
         ],
         verbose,
         [
-            "Setting up worker nodes",
-            "Could not setup worker nodes",
-            "Worker nodes ready",
+            "Waiting for the NFS server",
+            "NFS server did not start",
+            "NFS server ready",
         ],
         "k3s",
-        env,
+        HashMap::<&str, &str>::new(),
     )
-    .await
+    .await?;
+    command_no_print(
+        "kubectl",
+        &[
+            "exec",
+            "-n",
+            namespace,
+            "deployment/nfs-server",
+            "--",
+            "sh",
+            "-c",
+            "mkdir -p /exports/bench-storage /exports/scratch && chmod 777 /exports /exports/*",
+        ],
+        "k3s",
+        HashMap::<&str, &str>::new(),
+    )
+    .await?;
+
+    let images = STANDARD_IMAGES
+        .iter()
+        .map(|x| (x.path.to_owned(), x.name.to_owned(), "system"))
+        .chain(
+            config
+                .benchmark
+                .drivers
+                .iter()
+                .map(|x| (format!("../drivers/{x}"), x.clone(), "benches")),
+        );
+    for (path, name, repo) in images {
+        let tag = format!("{registry}/{repo}/{name}");
+        let args = ["build", "-t", &tag, &path];
+        let msgs = [
+            format!("Building {name} image"),
+            format!("Could not build {name} image"),
+            format!("Built {name} image"),
+        ];
+        retry(RetryStep::Images, &format!("build {name} image"), || {
+            command_print(
+                "docker",
+                &args,
+                verbose,
+                msgs.each_ref().map(|x| x.as_str()),
+                "k3s",
+                HashMap::<&str, &str>::new(),
+            )
+        })
+        .await?;
+        let args = ["push", &tag];
+        let msgs = [
+            format!("Pushing {name} image to {registry}"),
+            format!("Could not push {name} image to {registry}"),
+            format!("Pushed {name} image to {registry}"),
+        ];
+        retry(RetryStep::Images, &format!("push {name} image"), || {
+            command_print(
+                "docker",
+                &args,
+                verbose,
+                msgs.each_ref().map(|x| x.as_str()),
+                "k3s",
+                HashMap::<&str, &str>::new(),
+            )
+        })
+        .await?;
+    }
+    Ok(())
+}
+
+/// Put the namespaced objects of a manifest into the benchmark namespace, and bind the service
+/// account of that namespace, filling in what the playbooks template
+fn set_namespace(doc: &mut serde_yaml::Value, namespace: &str) {
+    match doc.get("kind").and_then(|x| x.as_str()) {
+        Some("PersistentVolume") => {}
+        Some("ClusterRoleBinding") => {
+            doc["metadata"]["name"] = format!("admin-user-{namespace}").into();
+            if let Some(subjects) = doc.get_mut("subjects").and_then(|x| x.as_sequence_mut()) {
+                for subject in subjects {
+                    subject["namespace"] = namespace.into();
+                }
+            }
+        }
+        _ => doc["metadata"]["namespace"] = namespace.into(),
+    }
+}
+
+/// Let the pods of a manifest run on any node, and turn node ports into cluster IPs, as the
+/// services are port-forwarded on an existing cluster
+fn unpin_from_master(doc: &mut serde_yaml::Value) {
+    if let Some(spec) = doc
+        .get_mut("spec")
+        .and_then(|x| x.get_mut("template"))
+        .and_then(|x| x.get_mut("spec"))
+        .and_then(|x| x.as_mapping_mut())
+    {
+        spec.remove("nodeSelector");
+    }
+    if doc.get("kind").and_then(|x| x.as_str()) != Some("Service") {
+        return;
+    }
+    if let Some(spec) = doc.get_mut("spec").and_then(|x| x.as_mapping_mut()) {
+        spec.remove("type");
+        if let Some(ports) = spec.get_mut("ports").and_then(|x| x.as_sequence_mut()) {
+            for port in ports.iter_mut().filter_map(|x| x.as_mapping_mut()) {
+                port.remove("nodePort");
+            }
+        }
+    }
 }
 
 async fn setup_platform(
@@ -300,10 +505,10 @@ async fn setup_platform(
 
             let info = p.platform_info(setup_args, verbose).await?;
             return Ok(PlatformConnectInfo {
+                host_username: Some(info.username(setup_args)),
                 private_key_file: info.ssh_key,
                 worker_ips: info.worker_ips,
                 master_ip: info.master_ip,
-                host_username: setup_args.host_username.clone(),
             });
         }
     }
@@ -311,13 +516,19 @@ async fn setup_platform(
 }
 
 pub async fn setup(args: &args::SetupArgs, cli: &Cli) -> Result<()> {
-    let config = parse_config(&cli.file)?;
-    let connect_args = match setup_platform(&config.setup, &args, cli.verbose).await {
+    let config = load_config(&cli.file)?;
+    set_retry_policy(config.retry.clone());
+    // with parallel drivers the providers create the nodes of every pool
+    let connect_args = match setup_platform(&config.cluster_setup(), &args, cli.verbose).await {
         Ok(p) => p,
         Err(err) => exit!(err, "Could not setup platform"),
     };
     info!("{connect_args:#?}");
 
+    if config.existing_cluster() {
+        return setup_existing_cluster(&config, cli.verbose).await;
+    }
+
     if connect_args.worker_ips.len() < 1 {
         exit!(
             "Check platform setup output",
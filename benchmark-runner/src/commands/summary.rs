@@ -0,0 +1,326 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use common::exit;
+use console::style;
+
+use crate::{
+    args::{Cli, SummaryArgs},
+    results::fetch_run_records,
+    stats::{scaling, summarize, Scaling, Summary},
+};
+
+use super::{
+    benchmark::{connect_db, platform_info},
+    validate::load_config,
+};
+
+const SCALING_HEADER: [&str; 7] = [
+    "driver",
+    "dataset",
+    "algorithm",
+    "nodes",
+    "speedup",
+    "efficiency",
+    "karp-flatt",
+];
+
+const HEADER: [&str; 15] = [
+    "driver",
+    "dataset",
+    "algorithm",
+    "nodes",
+    "n",
+    "mean",
+    "median",
+    "stddev",
+    "min",
+    "max",
+    "cv",
+    "95% ci",
+    "outliers",
+    "tainted",
+    "invalid",
+];
+
+pub async fn summary(args: &SummaryArgs, cli: &Cli) -> Result<()> {
+    let config = load_config(&cli.file)?;
+    let connect_args = platform_info(&config, cli.verbose).await?;
+    let mut connection = connect_db(&connect_args.master_ip).await?;
+
+    let records = fetch_run_records(&mut connection, None, args.campaign.clone()).await?;
+    if records.is_empty() {
+        exit!("", "No benchmark results to summarize");
+    }
+
+    let summaries = summarize(&records);
+    let scaling = scaling(&records);
+    print_summary(&summaries);
+    print_scaling(&scaling);
+    if let Some(report) = &args.report {
+        write_report(&summaries, &scaling, args.campaign.as_deref(), report, &[])?;
+        println!("Wrote report to {report}");
+    }
+    Ok(())
+}
+
+/// Table rows of the summaries, runtimes are in milliseconds
+fn rows(summaries: &[Summary]) -> Vec<[String; 15]> {
+    summaries
+        .iter()
+        .map(|s| {
+            let r = &s.runtime;
+            [
+                s.key.driver.clone(),
+                s.key.dataset.clone(),
+                s.key.algorithm.clone(),
+                s.key.nodes.to_string(),
+                r.n.to_string(),
+                format!("{:.1}", r.mean),
+                format!("{:.1}", r.median),
+                format!("{:.1}", r.stddev),
+                format!("{:.0}", r.min),
+                format!("{:.0}", r.max),
+                format!("{:.1}%", r.cv * 100.0),
+                r.ci.map(|(lo, hi)| format!("[{lo:.1}, {hi:.1}]"))
+                    .unwrap_or("-".to_owned()),
+                repeats(&s.outliers),
+                repeats(&s.tainted),
+                repeats(&s.invalid),
+            ]
+        })
+        .collect()
+}
+
+/// Repeat numbers of a table cell, `-` if there are none
+fn repeats(repeats: &[i64]) -> String {
+    if repeats.is_empty() {
+        "-".to_owned()
+    } else {
+        repeats
+            .iter()
+            .map(|x| format!("#{x}"))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+/// Pad the header & rows of a table to equal column widths,
+/// the first `left` columns are aligned left and the others right
+pub(crate) fn pad_columns<const N: usize>(
+    header: &[&str; N],
+    rows: &[[String; N]],
+    left: usize,
+) -> (Vec<String>, Vec<Vec<String>>) {
+    let mut widths = header.map(|x| x.len());
+    for row in rows {
+        for (w, cell) in widths.iter_mut().zip(row) {
+            *w = (*w).max(cell.len());
+        }
+    }
+
+    let line = |cells: &[String; N]| {
+        cells
+            .iter()
+            .zip(widths)
+            .enumerate()
+            .map(|(i, (cell, w))| {
+                if i < left {
+                    format!("{cell:<w$}")
+                } else {
+                    format!("{cell:>w$}")
+                }
+            })
+            .collect::<Vec<_>>()
+    };
+    (
+        line(&header.map(|x| x.to_uppercase())),
+        rows.iter().map(line).collect(),
+    )
+}
+
+/// Print the summaries as a table, highlighting noisy cells, outliers, tainted & invalid runs
+pub fn print_summary(summaries: &[Summary]) {
+    let (header, rows) = pad_columns(&HEADER, &rows(summaries), 3);
+    println!("\n{}", style(header.join("  ")).bold());
+    for (mut cells, s) in rows.into_iter().zip(summaries) {
+        if s.runtime.cv > 0.1 {
+            cells[10] = style(&cells[10]).yellow().to_string();
+        }
+        if !s.outliers.is_empty() {
+            cells[12] = style(&cells[12]).red().to_string();
+        }
+        if !s.tainted.is_empty() {
+            cells[13] = style(&cells[13]).red().to_string();
+        }
+        if !s.invalid.is_empty() {
+            cells[14] = style(&cells[14]).red().to_string();
+        }
+        println!("{}", cells.join("  "));
+    }
+    println!(
+        "{}",
+        style("runtimes in ms, outliers, tainted & invalid runs are repeat numbers").dim()
+    );
+}
+
+/// Table rows of the scaling metrics
+fn scaling_rows(scaling: &[Scaling]) -> Vec<[String; 7]> {
+    scaling
+        .iter()
+        .map(|s| {
+            [
+                s.key.driver.clone(),
+                s.key.dataset.clone(),
+                s.key.algorithm.clone(),
+                s.key.nodes.to_string(),
+                format!("{:.2}", s.speedup),
+                format!("{:.1}%", s.efficiency * 100.0),
+                s.karp_flatt
+                    .map(|x| format!("{x:.3}"))
+                    .unwrap_or("-".to_owned()),
+            ]
+        })
+        .collect()
+}
+
+/// Print the scaling metrics as a table, when more than one cluster size was benchmarked
+pub fn print_scaling(scaling: &[Scaling]) {
+    if scaling.iter().all(|x| x.key.nodes == x.base_nodes) {
+        return;
+    }
+    let (header, rows) = pad_columns(&SCALING_HEADER, &scaling_rows(scaling), 3);
+    println!("\n{}", style(header.join("  ")).bold());
+    for (mut cells, s) in rows.into_iter().zip(scaling) {
+        if s.efficiency < 0.5 {
+            cells[5] = style(&cells[5]).yellow().to_string();
+        }
+        println!("{}", cells.join("  "));
+    }
+    println!(
+        "{}",
+        style("relative to the smallest cluster size of each algorithm").dim()
+    );
+}
+
+/// Write the summaries, scaling metrics & charts to a report file,
+/// HTML if the file ends in `.html`, Markdown otherwise
+pub fn write_report(
+    summaries: &[Summary],
+    scaling: &[Scaling],
+    campaign: Option<&str>,
+    file: &str,
+    charts: &[PathBuf],
+) -> Result<()> {
+    let path = Path::new(file);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let title = match campaign {
+        Some(c) => format!("Benchmark summary: {c}"),
+        None => "Benchmark summary".to_owned(),
+    };
+
+    // charts are linked relative to the report where possible
+    let charts = charts
+        .iter()
+        .map(|x| {
+            path.parent()
+                .and_then(|p| x.strip_prefix(p).ok())
+                .unwrap_or(x)
+                .to_string_lossy()
+                .to_string()
+        })
+        .collect::<Vec<_>>();
+
+    let html = matches!(
+        path.extension().and_then(|x| x.to_str()),
+        Some("html" | "htm")
+    );
+    let mut report = if html {
+        format!(
+            "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{0}</title>\n<style>\n\
+            body {{ font-family: sans-serif; }}\n\
+            table {{ border-collapse: collapse; }}\n\
+            th, td {{ border: 1px solid #ccc; padding: 4px 8px; text-align: right; }}\n\
+            th:nth-child(-n+3), td:nth-child(-n+3) {{ text-align: left; }}\n\
+            </style>\n</head>\n<body>\n<h1>{0}</h1>\n",
+            escape(&title)
+        )
+    } else {
+        format!("# {title}\n")
+    };
+
+    let sections = [
+        (
+            "Runtime",
+            table(html, &HEADER, &rows(summaries)),
+            "Runtimes are in milliseconds, outliers are repeat numbers outside of 1.5 IQR of the quartiles. Tainted are the repeats during which a platform pod restarted, was OOM killed or evicted. Invalid are the repeats whose output didn't match the reference output of the dataset.",
+        ),
+        (
+            "Scaling",
+            table(html, &SCALING_HEADER, &scaling_rows(scaling)),
+            "Speedup, parallel efficiency & Karp-Flatt serial fraction relative to the smallest cluster size of each algorithm.",
+        ),
+    ];
+    for (heading, table, note) in sections {
+        if html {
+            report += &format!("<h2>{heading}</h2>\n{table}<p>{note}</p>\n");
+        } else {
+            report += &format!("\n## {heading}\n\n{table}\n{note}\n");
+        }
+    }
+
+    for chart in charts {
+        if html {
+            report += &format!("<img src=\"{0}\" alt=\"{0}\">\n", escape(&chart));
+        } else {
+            report += &format!("\n![{chart}]({chart})\n");
+        }
+    }
+    if html {
+        report += "</body>\n</html>\n";
+    }
+    std::fs::write(path, report)?;
+    Ok(())
+}
+
+fn escape(x: &str) -> String {
+    x.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// A Markdown or HTML table, the first 3 columns are aligned left and the others right
+fn table<const N: usize>(html: bool, header: &[&str; N], rows: &[[String; N]]) -> String {
+    if html {
+        let mut s = format!(
+            "<table>\n<tr>{}</tr>\n",
+            header
+                .iter()
+                .map(|x| format!("<th>{}</th>", escape(x)))
+                .collect::<String>()
+        );
+        for row in rows {
+            s += &format!(
+                "<tr>{}</tr>\n",
+                row.iter()
+                    .map(|x| format!("<td>{}</td>", escape(x)))
+                    .collect::<String>()
+            );
+        }
+        s + "</table>\n"
+    } else {
+        let mut s = format!("| {} |\n", header.join(" | "));
+        s += &format!(
+            "|{}\n",
+            (0..N)
+                .map(|i| if i < 3 { " --- |" } else { " ---: |" })
+                .collect::<String>()
+        );
+        for row in rows {
+            s += &format!("| {} |\n", row.join(" | "));
+        }
+        s
+    }
+}
@@ -0,0 +1,188 @@
+use std::path::Path;
+
+use anyhow::Result;
+use common::{
+    command::{GREEN_TICK, RED_CROSS},
+    config::{parse_config, Config, ConfigError, ALGORITHMS},
+    exit,
+};
+
+use crate::args::Cli;
+
+use super::benchmark::types::DatasetUserConfig;
+
+/// Validate the configuration against the compiled in providers & drivers, and the datasets on disk
+pub fn check_config(config: &Config) -> Vec<ConfigError> {
+    let providers = base_provider::PROVIDERS
+        .iter()
+        .map(|x| x.name())
+        .collect::<Vec<String>>();
+    let drivers = base_driver::DRIVER_CONFIGS
+        .iter()
+        .map(|x| x.name())
+        .collect::<Vec<String>>();
+    let mut errors = config.validate(&providers, &drivers);
+
+    let provider = base_provider::PROVIDERS
+        .iter()
+        .find(|x| x.name() == config.setup.provider);
+    let uses_platform_dir = provider.is_none_or(|x| x.uses_platform_dir());
+    if uses_platform_dir && config.setup.platform.is_empty() {
+        errors.push(ConfigError::new("setup.platform", "must not be empty"));
+    } else if uses_platform_dir
+        && !Path::new(&format!("platforms/{}", config.setup.platform)).is_dir()
+    {
+        errors.push(ConfigError::new(
+            "setup.platform",
+            format!("platforms/{} does not exist", config.setup.platform),
+        ));
+    }
+
+    match provider.map(|x| x.provides_cluster()) {
+        Some(false) if config.existing_cluster() => errors.push(ConfigError::new(
+            "setup.provider",
+            format!(
+                "must be one of {} when kubernetes.kubeconfig is set",
+                base_provider::PROVIDERS
+                    .iter()
+                    .filter(|x| x.provides_cluster())
+                    .map(|x| format!("`{}`", x.name()))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        )),
+        Some(true) if !config.existing_cluster() => errors.push(ConfigError::new(
+            "kubernetes.kubeconfig",
+            format!(
+                "the kubeconfig of the cluster is required by the `{}` provider",
+                config.setup.provider
+            ),
+        )),
+        _ => {}
+    }
+
+    for (i, dataset) in config.benchmark.datasets.iter().enumerate() {
+        let key = format!("benchmark.datasets[{i}]");
+        for file in [format!("{dataset}.v"), format!("{dataset}.e")] {
+            if !Path::new(&format!("datasets/{dataset}/{file}")).is_file() {
+                errors.push(ConfigError::new(
+                    &key,
+                    format!("datasets/{dataset}/{file} does not exist"),
+                ));
+            }
+        }
+
+        let file = format!("datasets/{dataset}/config.toml");
+        let d: DatasetUserConfig = match std::fs::read_to_string(&file) {
+            Ok(s) => match toml::from_str(&s) {
+                Ok(d) => d,
+                Err(err) => {
+                    errors.push(ConfigError::new(
+                        &key,
+                        format!("could not parse {file}: {}", err.message()),
+                    ));
+                    continue;
+                }
+            },
+            Err(_) => {
+                errors.push(ConfigError::new(&key, format!("{file} does not exist")));
+                continue;
+            }
+        };
+
+        for algo in d.skip_algos.unwrap_or_default() {
+            if !ALGORITHMS.contains(&algo.as_str()) {
+                errors.push(ConfigError::new(
+                    &key,
+                    format!("unknown algorithm `{algo}` in skip_algos of {file}"),
+                ));
+            }
+        }
+    }
+
+    errors
+}
+
+/// Parse & validate the configuration, exiting with every problem found
+pub fn load_config(file: &str) -> Result<Config> {
+    let config = parse_config(file)?;
+    let errors = check_config(&config);
+    if !errors.is_empty() {
+        errors.iter().for_each(|x| eprintln!("{} {x}", *RED_CROSS));
+        exit!(
+            format!("{} configuration errors", errors.len()),
+            "Invalid configuration file {}",
+            file
+        );
+    }
+    Ok(config)
+}
+
+pub async fn validate(cli: &Cli) -> Result<()> {
+    load_config(&cli.file)?;
+    println!("{} Configuration {} is valid", *GREEN_TICK, cli.file);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(provider: &str, platform: &str) -> Config {
+        toml::from_str(&format!(
+            r#"
+            [setup]
+            provider = "{provider}"
+            platform = "{platform}"
+            node_configs = [2]
+
+            [benchmark]
+            drivers = ["native"]
+            datasets = ["tiny"]
+            repeat = 1
+            "#
+        ))
+        .unwrap()
+    }
+
+    fn errors(config: &Config, key: &str) -> Vec<String> {
+        check_config(config)
+            .into_iter()
+            .filter(|x| x.key == key)
+            .map(|x| x.message)
+            .collect()
+    }
+
+    fn platform_errors(provider: &str, platform: &str) -> Vec<String> {
+        errors(&config(provider, platform), "setup.platform")
+    }
+
+    #[test]
+    fn platform_required_by_providers_using_it() {
+        assert_eq!(platform_errors("vagrant", ""), ["must not be empty"]);
+        assert_eq!(
+            platform_errors("vagrant", "missing"),
+            ["platforms/missing does not exist"]
+        );
+        assert!(platform_errors("static", "").is_empty());
+        assert!(platform_errors("existing", "").is_empty());
+    }
+    #[test]
+    fn kubeconfig_only_with_providers_of_a_cluster() {
+        let mut c = config("k3d", "");
+        assert_eq!(
+            errors(&c, "kubernetes.kubeconfig"),
+            ["the kubeconfig of the cluster is required by the `k3d` provider"]
+        );
+
+        c.kubernetes = Some(toml::from_str(r#"kubeconfig = "kube-config""#).unwrap());
+        assert!(errors(&c, "kubernetes.kubeconfig").is_empty());
+        assert!(errors(&c, "setup.provider").is_empty());
+
+        c.setup.provider = "static".to_owned();
+        assert_eq!(
+            errors(&c, "setup.provider"),
+            ["must be one of `k3d`, `existing` when kubernetes.kubeconfig is set"]
+        );
+    }
+}
@@ -9,8 +9,11 @@ mod args;
 mod commands;
 mod metrics_utils;
 mod model;
+mod report;
+mod results;
 mod rpc;
 mod schema;
+mod stats;
 
 #[tokio::main]
 async fn main() -> Result<()> {
@@ -26,7 +29,16 @@ async fn main() -> Result<()> {
     let args = Cli::parse();
     match &args.command {
         Commands::Setup(setup) => commands::setup::setup(setup, &args).await,
-        Commands::Benchmark(_) => commands::benchmark::run_benchmark(&args).await,
+        Commands::Benchmark(bench) => commands::benchmark::run_benchmark(bench, &args).await,
+        Commands::Plan => commands::plan::plan(&args).await,
+        Commands::Validate => commands::validate::validate(&args).await,
+        Commands::Export(export) => commands::export::export(export, &args).await,
+        Commands::Summary(summary) => commands::summary::summary(summary, &args).await,
+        Commands::Compare(compare) => commands::compare::compare(compare, &args).await,
+        Commands::Report(report) => commands::report::report(report).await,
+        Commands::Campaigns(campaigns) => match campaigns.command {
+            CampaignsCommands::Ls => commands::campaigns::list(&args).await,
+        },
         Commands::Destroy => commands::destroy::destroy(&args).await,
         Commands::Ls => commands::ls::list(&args).await,
         Commands::Dashboard => commands::port_forwards::dashboard(&args).await,
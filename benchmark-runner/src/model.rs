@@ -1,3 +1,4 @@
+use chrono::NaiveDateTime;
 use diesel::prelude::*;
 
 #[derive(Queryable, Selectable, Insertable)]
@@ -5,4 +6,135 @@ use diesel::prelude::*;
 pub struct Benchmark {
     pub id: i32,
     pub nodes: i32,
+    pub driver: Option<String>,
+    pub dataset: Option<String>,
+    pub algorithm: Option<String>,
+    pub repeat: Option<i32>,
+    pub started_at: Option<NaiveDateTime>,
+    pub finished_at: Option<NaiveDateTime>,
+    pub status: String,
+    pub config_hash: Option<String>,
+    pub campaign_id: Option<i32>,
+    pub status_reason: Option<String>,
+    pub tainted: bool,
+    pub validation: Option<String>,
+    pub validation_message: Option<String>,
+}
+
+#[derive(Clone, Copy, Insertable)]
+#[diesel(table_name = crate::schema::benchmarks)]
+pub struct NewBenchmark<'a> {
+    pub nodes: i32,
+    pub driver: &'a str,
+    pub dataset: &'a str,
+    pub algorithm: &'a str,
+    pub repeat: i32,
+    pub status: &'a str,
+    pub config_hash: &'a str,
+    pub campaign_id: i32,
+}
+
+#[derive(Queryable, Selectable)]
+#[diesel(table_name = crate::schema::campaigns)]
+pub struct Campaign {
+    pub id: i32,
+    pub name: String,
+    pub labels: Vec<String>,
+    pub notes: Option<String>,
+    pub git_commit: Option<String>,
+    pub started_at: NaiveDateTime,
+    pub finished_at: Option<NaiveDateTime>,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = crate::schema::campaigns)]
+pub struct NewCampaign<'a> {
+    pub name: &'a str,
+    pub labels: &'a [String],
+    pub notes: Option<&'a str>,
+    pub git_commit: Option<&'a str>,
+    pub config: &'a str,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = crate::schema::run_events)]
+pub struct NewRunEvent<'a> {
+    pub run_id: i32,
+    pub pod: &'a str,
+    pub kind: &'a str,
+    pub reason: Option<&'a str>,
+    pub message: Option<&'a str>,
+    pub occurred_at: NaiveDateTime,
+}
+
+/// Lifecycle of a run, stored in the `status` column of `benchmarks`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RunStatus {
+    Pending,
+    Running,
+    Completed,
+    /// The driver crashed or its benchmark job failed, the reason is stored in `status_reason`
+    Failed,
+    /// The run exceeded one of the `benchmark.timeouts`
+    TimedOut,
+}
+
+impl RunStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RunStatus::Pending => "pending",
+            RunStatus::Running => "running",
+            RunStatus::Completed => "completed",
+            RunStatus::Failed => "failed",
+            RunStatus::TimedOut => "timed_out",
+        }
+    }
+}
+
+/// Outcome of comparing the output of a run with the reference output of its dataset, stored in
+/// the `validation` column of `benchmarks`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Validation {
+    Passed,
+    /// The output differs from the reference, the differences are stored in `validation_message`
+    Failed,
+    /// The driver wrote no output for the run
+    Missing,
+}
+
+impl Validation {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Validation::Passed => "passed",
+            Validation::Failed => "failed",
+            Validation::Missing => "missing",
+        }
+    }
+}
+
+/// What happened to a platform pod during a run, stored in the `kind` column of `run_events`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RunEventKind {
+    /// A container of the pod restarted
+    Restart,
+    /// A container of the pod was killed for running out of memory
+    OomKilled,
+    /// The pod was evicted from its node
+    Evicted,
+    /// The pod no longer exists, it was deleted or rescheduled under another name
+    Missing,
+    /// A warning event kubernetes reported for the pod
+    Warning,
+}
+
+impl RunEventKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RunEventKind::Restart => "restart",
+            RunEventKind::OomKilled => "oom_killed",
+            RunEventKind::Evicted => "evicted",
+            RunEventKind::Missing => "missing",
+            RunEventKind::Warning => "warning",
+        }
+    }
 }
@@ -0,0 +1,256 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    path::{Path, PathBuf},
+};
+
+use anyhow::Result;
+use plotters::{
+    coord::{types::RangedCoordf64, Shift},
+    prelude::*,
+    style::text_anchor::{HPos, Pos, VPos},
+};
+use regex::Regex;
+
+use crate::{
+    results::RunRecord,
+    stats::{group_cells, scaling},
+};
+
+const CHART_SIZE: (u32, u32) = (1024, 640);
+
+/// Render the charts of the runs to `dir`, using the mean runtime over the repeats of each cell:
+/// - `bars-<driver>-<nodes>.svg`: runtime of every algorithm per dataset
+/// - `lines-<driver>-<dataset>.svg`: runtime of every algorithm per cluster size
+/// - `speedup-<driver>-<dataset>.svg`: speedup of every algorithm per cluster size, if several were run
+pub fn render_charts(records: &[RunRecord], dir: &Path) -> Result<Vec<PathBuf>> {
+    std::fs::create_dir_all(dir)?;
+    let r = Regex::new(r#"[^A-Za-z0-9_-]"#)?;
+    let slug = |x: &str| match r.replace_all(x, "").to_string() {
+        s if s.is_empty() || s == "-" => "unknown".to_owned(),
+        s => s,
+    };
+
+    let mut bars: BTreeMap<(String, i32), BTreeMap<(String, String), f64>> = BTreeMap::new();
+    let mut lines: BTreeMap<(String, String), BTreeMap<(String, i32), f64>> = BTreeMap::new();
+    for (key, runs) in group_cells(records) {
+        let mean = runs.iter().map(|x| x.runtime_ms as f64).sum::<f64>() / runs.len() as f64;
+        bars.entry((key.driver.clone(), key.nodes))
+            .or_default()
+            .insert((key.algorithm.clone(), key.dataset.clone()), mean);
+        lines
+            .entry((key.driver, key.dataset))
+            .or_default()
+            .insert((key.algorithm, key.nodes), mean);
+    }
+    let mut speedups: BTreeMap<(String, String), BTreeMap<(String, i32), f64>> = BTreeMap::new();
+    for s in scaling(records) {
+        speedups
+            .entry((s.key.driver, s.key.dataset))
+            .or_default()
+            .insert((s.key.algorithm, s.key.nodes), s.speedup);
+    }
+
+    let mut files = Vec::new();
+    for ((driver, nodes), cells) in &bars {
+        let file = dir.join(format!("bars-{}-{nodes}.svg", slug(driver)));
+        bar_chart(
+            &file,
+            &format!("Runtime per algorithm, {driver} on {nodes} nodes"),
+            cells,
+        )?;
+        files.push(file);
+    }
+    for ((driver, dataset), cells) in &lines {
+        let file = dir.join(format!("lines-{}-{}.svg", slug(driver), slug(dataset)));
+        line_chart(
+            &file,
+            &format!("Runtime per cluster size, {driver} on {dataset}"),
+            "Time (ms)",
+            cells,
+            false,
+        )?;
+        files.push(file);
+    }
+    for ((driver, dataset), cells) in &speedups {
+        if cells.keys().map(|(_, n)| n).collect::<BTreeSet<_>>().len() < 2 {
+            continue;
+        }
+        let file = dir.join(format!("speedup-{}-{}.svg", slug(driver), slug(dataset)));
+        line_chart(
+            &file,
+            &format!("Speedup per cluster size, {driver} on {dataset}"),
+            "Speedup",
+            cells,
+            true,
+        )?;
+        files.push(file);
+    }
+    Ok(files)
+}
+
+/// Grouped bar chart of the runtime of each algorithm, with a bar per dataset
+fn bar_chart(file: &Path, title: &str, cells: &BTreeMap<(String, String), f64>) -> Result<()> {
+    let algorithms = cells
+        .keys()
+        .map(|(a, _)| a.as_str())
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect::<Vec<_>>();
+    let datasets = cells
+        .keys()
+        .map(|(_, d)| d.as_str())
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect::<Vec<_>>();
+    let max = cells.values().copied().fold(0.0, f64::max);
+
+    let root = SVGBackend::new(file, CHART_SIZE).into_drawing_area();
+    root.fill(&WHITE)?;
+    let mut chart = ChartBuilder::on(&root)
+        .caption(title, ("sans-serif", 24))
+        .margin(16)
+        .x_label_area_size(40)
+        .y_label_area_size(64)
+        .build_cartesian_2d(0.0..algorithms.len() as f64, 0.0..(max * 1.15).max(1.0))?;
+    chart
+        .configure_mesh()
+        .disable_x_mesh()
+        .x_labels(0)
+        .x_desc("Algorithm")
+        .y_desc("Time (ms)")
+        .draw()?;
+    let labels = algorithms
+        .iter()
+        .enumerate()
+        .map(|(i, x)| (i as f64 + 0.5, x.to_string()))
+        .collect::<Vec<_>>();
+    draw_x_labels(&root, &chart, &labels)?;
+
+    let width = 0.8 / datasets.len() as f64;
+    for (j, dataset) in datasets.iter().enumerate() {
+        let color = Palette99::pick(j).mix(0.9);
+        chart
+            .draw_series(algorithms.iter().enumerate().filter_map(|(i, algo)| {
+                let time = cells.get(&(algo.to_string(), dataset.to_string()))?;
+                let x = i as f64 + 0.1 + j as f64 * width;
+                Some(Rectangle::new(
+                    [(x, 0.0), (x + width, *time)],
+                    color.filled(),
+                ))
+            }))?
+            .label(*dataset)
+            .legend(move |(x, y)| Rectangle::new([(x, y - 5), (x + 10, y + 5)], color.filled()));
+    }
+    chart
+        .configure_series_labels()
+        .background_style(WHITE.mix(0.8))
+        .border_style(BLACK)
+        .draw()?;
+    root.present()?;
+    Ok(())
+}
+
+/// Line chart of a value of each algorithm over the cluster sizes, spaced uniformly. The ideal
+/// linear speedup relative to the smallest cluster is drawn as a dashed line if `ideal` is set
+fn line_chart(
+    file: &Path,
+    title: &str,
+    y_desc: &str,
+    cells: &BTreeMap<(String, i32), f64>,
+    ideal: bool,
+) -> Result<()> {
+    let algorithms = cells
+        .keys()
+        .map(|(a, _)| a.as_str())
+        .collect::<BTreeSet<_>>();
+    let nodes = cells
+        .keys()
+        .map(|(_, n)| *n)
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect::<Vec<_>>();
+    let ideal = if ideal {
+        nodes
+            .iter()
+            .enumerate()
+            .map(|(i, n)| (i as f64, *n as f64 / nodes[0] as f64))
+            .collect::<Vec<_>>()
+    } else {
+        Vec::new()
+    };
+    let max = cells
+        .values()
+        .copied()
+        .chain(ideal.iter().map(|(_, y)| *y))
+        .fold(0.0, f64::max);
+
+    let root = SVGBackend::new(file, CHART_SIZE).into_drawing_area();
+    root.fill(&WHITE)?;
+    let mut chart = ChartBuilder::on(&root)
+        .caption(title, ("sans-serif", 24))
+        .margin(16)
+        .x_label_area_size(40)
+        .y_label_area_size(64)
+        .build_cartesian_2d(-0.5..nodes.len() as f64 - 0.5, 0.0..(max * 1.15).max(1.0))?;
+    chart
+        .configure_mesh()
+        .disable_x_mesh()
+        .x_labels(0)
+        .x_desc("Nodes")
+        .y_desc(y_desc)
+        .draw()?;
+    let labels = nodes
+        .iter()
+        .enumerate()
+        .map(|(i, x)| (i as f64, x.to_string()))
+        .collect::<Vec<_>>();
+    draw_x_labels(&root, &chart, &labels)?;
+
+    if !ideal.is_empty() {
+        chart
+            .draw_series(DashedLineSeries::new(ideal, 6, 4, BLACK.mix(0.5).into()))?
+            .label("ideal")
+            .legend(|(x, y)| PathElement::new([(x, y), (x + 16, y)], BLACK.mix(0.5)));
+    }
+    for (j, algo) in algorithms.iter().enumerate() {
+        let color = Palette99::pick(j).to_rgba();
+        let points = nodes
+            .iter()
+            .enumerate()
+            .filter_map(|(i, n)| Some((i as f64, *cells.get(&(algo.to_string(), *n))?)))
+            .collect::<Vec<_>>();
+        chart
+            .draw_series(LineSeries::new(points.clone(), color.stroke_width(2)))?
+            .label(*algo)
+            .legend(move |(x, y)| PathElement::new([(x, y), (x + 16, y)], color.stroke_width(2)));
+        chart.draw_series(
+            points
+                .into_iter()
+                .map(|p| Circle::new(p, 4, color.filled())),
+        )?;
+    }
+    chart
+        .configure_series_labels()
+        .background_style(WHITE.mix(0.8))
+        .border_style(BLACK)
+        .draw()?;
+    root.present()?;
+    Ok(())
+}
+
+/// Draw category labels below the x axis, as the axis itself only labels numeric values
+fn draw_x_labels(
+    root: &DrawingArea<SVGBackend, Shift>,
+    chart: &ChartContext<SVGBackend, Cartesian2d<RangedCoordf64, RangedCoordf64>>,
+    labels: &[(f64, String)],
+) -> Result<()> {
+    let style = ("sans-serif", 14)
+        .into_font()
+        .color(&BLACK)
+        .pos(Pos::new(HPos::Center, VPos::Top));
+    for (x, label) in labels {
+        let (px, py) = chart.backend_coord(&(*x, 0.0));
+        root.draw(&Text::new(label.as_str(), (px, py + 6), style.clone()))?;
+    }
+    Ok(())
+}
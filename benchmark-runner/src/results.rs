@@ -0,0 +1,155 @@
+use std::{collections::HashMap, path::Path};
+
+use anyhow::{Context, Result};
+use common::exit;
+use diesel::{
+    sql_query,
+    sql_types::{Array, BigInt, Bool, Double, Int4, Integer, Nullable, Text},
+    QueryableByName,
+};
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+use serde::{Deserialize, Serialize};
+
+use crate::stats::{scaling, CellKey};
+
+/// One algorithm run, joined from the `benchmarks`, `campaigns`, `gn_test` and `performance_metrics`
+/// tables. Times are in milliseconds, cpu & ram are summaries over every recorded pod sample of the run.
+/// The scaling metrics are those of the benchmark cell of the run, see [`scaling`]
+#[derive(Debug, Clone, Serialize, Deserialize, QueryableByName)]
+pub struct RunRecord {
+    #[diesel(sql_type = Integer)]
+    pub run_id: i32,
+    #[diesel(sql_type = Nullable<Text>)]
+    pub campaign: Option<String>,
+    #[diesel(sql_type = Nullable<Text>)]
+    pub driver: Option<String>,
+    #[diesel(sql_type = Text)]
+    pub dataset: String,
+    #[diesel(sql_type = Text)]
+    pub algorithm: String,
+    #[diesel(sql_type = Integer)]
+    pub nodes: i32,
+    #[diesel(sql_type = BigInt)]
+    pub repeat: i64,
+    #[diesel(sql_type = Nullable<Integer>)]
+    pub vertices: Option<i32>,
+    #[diesel(sql_type = Nullable<Integer>)]
+    pub edges: Option<i32>,
+    #[diesel(sql_type = Nullable<Integer>)]
+    pub load_ms: Option<i32>,
+    #[diesel(sql_type = Integer)]
+    pub runtime_ms: i32,
+    #[diesel(sql_type = Integer)]
+    pub makespan_ms: i32,
+    #[diesel(sql_type = Nullable<Double>)]
+    pub cpu_avg: Option<f64>,
+    #[diesel(sql_type = Nullable<Double>)]
+    pub cpu_max: Option<f64>,
+    #[diesel(sql_type = Nullable<Double>)]
+    pub ram_avg: Option<f64>,
+    #[diesel(sql_type = Nullable<Double>)]
+    pub ram_max: Option<f64>,
+    #[diesel(sql_type = Text)]
+    pub status: String,
+    /// A platform pod restarted, was OOM killed or evicted during the run
+    #[diesel(sql_type = Bool)]
+    #[serde(default)]
+    pub tainted: bool,
+    /// Whether the output of the run matched the reference, `passed`, `failed` or `missing`
+    #[diesel(sql_type = Nullable<Text>)]
+    #[serde(default)]
+    pub validation: Option<String>,
+    #[diesel(sql_type = Nullable<Text>)]
+    pub started_at: Option<String>,
+    #[diesel(sql_type = Nullable<Text>)]
+    pub finished_at: Option<String>,
+    #[diesel(sql_type = Nullable<Text>)]
+    pub config_hash: Option<String>,
+    #[diesel(sql_type = Nullable<Double>)]
+    pub speedup: Option<f64>,
+    #[diesel(sql_type = Nullable<Double>)]
+    pub efficiency: Option<f64>,
+    #[diesel(sql_type = Nullable<Double>)]
+    pub karp_flatt: Option<f64>,
+}
+
+const RUN_RECORDS_QUERY: &str = r#"
+WITH runtime AS (
+    SELECT id, algo, dataset, time, vertex, edge,
+        row_number() OVER (PARTITION BY id ORDER BY seq) - 1 AS n
+    FROM gn_test WHERE type = 'runtime'
+), loading AS (
+    SELECT id, max(time) AS time FROM gn_test WHERE type = 'loading' GROUP BY id
+), metrics AS (
+    SELECT run_id,
+        avg(cpu_usage) AS cpu_avg, max(cpu_usage) AS cpu_max,
+        avg(ram_usage) AS ram_avg, max(ram_usage) AS ram_max
+    FROM performance_metrics GROUP BY run_id
+)
+SELECT b.id AS run_id, c.name AS campaign, b.driver, r.dataset, r.algo AS algorithm, b.nodes,
+    coalesce(b.repeat, 0) + r.n AS repeat,
+    r.vertex AS vertices, r.edge AS edges, l.time AS load_ms, r.time AS runtime_ms,
+    CASE WHEN r.n = 0 THEN r.time + coalesce(l.time, 0) ELSE r.time END AS makespan_ms,
+    m.cpu_avg, m.cpu_max, m.ram_avg, m.ram_max, b.status, b.tainted,
+    b.validation,
+    to_char(b.started_at, 'YYYY-MM-DD"T"HH24:MI:SS.MS') AS started_at,
+    to_char(b.finished_at, 'YYYY-MM-DD"T"HH24:MI:SS.MS') AS finished_at,
+    b.config_hash, NULL::float8 AS speedup, NULL::float8 AS efficiency, NULL::float8 AS karp_flatt
+FROM benchmarks b
+JOIN runtime r ON r.id = b.id
+LEFT JOIN loading l ON l.id = b.id
+LEFT JOIN metrics m ON m.run_id = b.id
+LEFT JOIN campaigns c ON c.id = b.campaign_id
+WHERE ($1::int4[] IS NULL OR b.id = ANY($1)) AND ($2::text IS NULL OR c.name = $2)
+ORDER BY b.id, r.n
+"#;
+
+/// Fetch the results of every run, or only of `run_ids` and `campaign` if given
+pub async fn fetch_run_records(
+    conn: &mut AsyncPgConnection,
+    run_ids: Option<Vec<i32>>,
+    campaign: Option<String>,
+) -> Result<Vec<RunRecord>> {
+    let mut records = sql_query(RUN_RECORDS_QUERY)
+        .bind::<Nullable<Array<Int4>>, _>(run_ids)
+        .bind::<Nullable<Text>, _>(campaign)
+        .load::<RunRecord>(conn)
+        .await
+        .context("Could not query benchmark results")?;
+
+    let scaling = scaling(&records)
+        .into_iter()
+        .map(|x| (x.key.clone(), x))
+        .collect::<HashMap<_, _>>();
+    for record in records.iter_mut() {
+        if let Some(s) = scaling.get(&CellKey::of(record)) {
+            record.speedup = Some(s.speedup);
+            record.efficiency = Some(s.efficiency);
+            record.karp_flatt = s.karp_flatt;
+        }
+    }
+    Ok(records)
+}
+
+/// Read run records from a file written by `export`, as CSV or JSON lines
+pub fn read_run_records(file: &str) -> Result<Vec<RunRecord>> {
+    match Path::new(file).extension().and_then(|x| x.to_str()) {
+        Some("csv") => csv::Reader::from_path(file)
+            .with_context(|| format!("Could not read {file}"))?
+            .deserialize()
+            .collect::<Result<Vec<RunRecord>, _>>()
+            .with_context(|| format!("Could not parse {file}")),
+        Some("json" | "jsonl") => std::fs::read_to_string(file)
+            .with_context(|| format!("Could not read {file}"))?
+            .lines()
+            .filter(|x| !x.trim().is_empty())
+            .map(serde_json::from_str)
+            .collect::<Result<Vec<RunRecord>, _>>()
+            .with_context(|| format!("Could not parse {file}")),
+        _ => exit!(
+            "",
+            "Unsupported results file {}, expected .csv or .jsonl",
+            file
+        ),
+    }
+}
@@ -4,5 +4,59 @@ diesel::table! {
     benchmarks (id) {
         id -> Int4,
         nodes -> Int4,
+        #[max_length = 256]
+        driver -> Nullable<Varchar>,
+        #[max_length = 256]
+        dataset -> Nullable<Varchar>,
+        #[max_length = 256]
+        algorithm -> Nullable<Varchar>,
+        repeat -> Nullable<Int4>,
+        started_at -> Nullable<Timestamp>,
+        finished_at -> Nullable<Timestamp>,
+        #[max_length = 32]
+        status -> Varchar,
+        #[max_length = 64]
+        config_hash -> Nullable<Varchar>,
+        campaign_id -> Nullable<Int4>,
+        status_reason -> Nullable<Text>,
+        tainted -> Bool,
+        #[max_length = 32]
+        validation -> Nullable<Varchar>,
+        validation_message -> Nullable<Text>,
     }
 }
+
+diesel::table! {
+    campaigns (id) {
+        id -> Int4,
+        #[max_length = 256]
+        name -> Varchar,
+        labels -> Array<Text>,
+        notes -> Nullable<Text>,
+        #[max_length = 64]
+        git_commit -> Nullable<Varchar>,
+        config -> Text,
+        started_at -> Timestamp,
+        finished_at -> Nullable<Timestamp>,
+    }
+}
+
+diesel::table! {
+    run_events (id) {
+        id -> Int4,
+        run_id -> Int4,
+        #[max_length = 256]
+        pod -> Varchar,
+        #[max_length = 32]
+        kind -> Varchar,
+        #[max_length = 256]
+        reason -> Nullable<Varchar>,
+        message -> Nullable<Text>,
+        occurred_at -> Timestamp,
+    }
+}
+
+diesel::joinable!(benchmarks -> campaigns (campaign_id));
+diesel::joinable!(run_events -> benchmarks (run_id));
+
+diesel::allow_tables_to_appear_in_same_query!(benchmarks, campaigns, run_events,);
@@ -0,0 +1,544 @@
+use std::collections::BTreeMap;
+
+use crate::results::RunRecord;
+
+/// Two-sided 95% critical values of Student's t distribution for 1 to 30 degrees of freedom,
+/// the normal approximation is used above that
+const T_95: [f64; 30] = [
+    12.706, 4.303, 3.182, 2.776, 2.571, 2.447, 2.365, 2.306, 2.262, 2.228, 2.201, 2.179, 2.160,
+    2.145, 2.131, 2.120, 2.110, 2.101, 2.093, 2.086, 2.080, 2.074, 2.069, 2.064, 2.060, 2.056,
+    2.052, 2.048, 2.045, 2.042,
+];
+
+/// Descriptive statistics of a sample
+#[derive(Debug, Clone)]
+pub struct Stats {
+    pub n: usize,
+    pub mean: f64,
+    pub median: f64,
+    /// Sample standard deviation, 0 for a single value
+    pub stddev: f64,
+    pub min: f64,
+    pub max: f64,
+    /// Coefficient of variation, stddev relative to the mean
+    pub cv: f64,
+    /// 95% confidence interval of the mean, needs at least 2 values
+    pub ci: Option<(f64, f64)>,
+}
+
+impl Stats {
+    pub fn new(values: &[f64]) -> Option<Self> {
+        if values.is_empty() {
+            return None;
+        }
+
+        let mut sorted = values.to_vec();
+        sorted.sort_by(f64::total_cmp);
+        let n = sorted.len();
+        let mean = sorted.iter().sum::<f64>() / n as f64;
+        let stddev = if n > 1 {
+            (sorted.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1) as f64).sqrt()
+        } else {
+            0.0
+        };
+        let ci = (n > 1).then(|| {
+            let t = T_95.get(n - 2).copied().unwrap_or(1.96);
+            let margin = t * stddev / (n as f64).sqrt();
+            (mean - margin, mean + margin)
+        });
+
+        Some(Self {
+            n,
+            mean,
+            median: quantile(&sorted, 0.5),
+            stddev,
+            min: sorted[0],
+            max: sorted[n - 1],
+            cv: if mean != 0.0 { stddev / mean } else { 0.0 },
+            ci,
+        })
+    }
+}
+
+/// Quantile of sorted values, interpolating linearly between the closest ranks
+pub fn quantile(sorted: &[f64], q: f64) -> f64 {
+    let pos = q * (sorted.len() - 1) as f64;
+    let (lo, hi) = (pos.floor() as usize, pos.ceil() as usize);
+    sorted[lo] + (sorted[hi] - sorted[lo]) * (pos - lo as f64)
+}
+
+/// Indices of the values outside of Tukey's fences (1.5 IQR beyond the quartiles),
+/// only flagged for samples of at least 4 values
+pub fn outliers(values: &[f64]) -> Vec<usize> {
+    if values.len() < 4 {
+        return Vec::new();
+    }
+    let mut sorted = values.to_vec();
+    sorted.sort_by(f64::total_cmp);
+    let (q1, q3) = (quantile(&sorted, 0.25), quantile(&sorted, 0.75));
+    let iqr = q3 - q1;
+    let (lo, hi) = (q1 - 1.5 * iqr, q3 + 1.5 * iqr);
+    values
+        .iter()
+        .enumerate()
+        .filter(|(_, x)| **x < lo || **x > hi)
+        .map(|(i, _)| i)
+        .collect()
+}
+
+/// A benchmark cell, every repeat of an algorithm by a driver on a dataset & cluster size
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CellKey {
+    pub driver: String,
+    pub dataset: String,
+    pub algorithm: String,
+    pub nodes: i32,
+}
+
+impl CellKey {
+    pub fn of(record: &RunRecord) -> Self {
+        Self {
+            driver: record.driver.clone().unwrap_or("-".to_owned()),
+            dataset: record.dataset.clone(),
+            algorithm: record.algorithm.clone(),
+            nodes: record.nodes,
+        }
+    }
+}
+
+/// Group the run records by benchmark cell
+pub fn group_cells(records: &[RunRecord]) -> BTreeMap<CellKey, Vec<&RunRecord>> {
+    let mut cells: BTreeMap<CellKey, Vec<&RunRecord>> = BTreeMap::new();
+    for record in records {
+        cells.entry(CellKey::of(record)).or_default().push(record);
+    }
+    cells
+}
+
+/// Runtime statistics of the repeats of a benchmark cell
+#[derive(Debug, Clone)]
+pub struct Summary {
+    pub key: CellKey,
+    pub runtime: Stats,
+    /// Repeats whose runtime is an outlier
+    pub outliers: Vec<i64>,
+    /// Repeats during which a platform pod was disrupted
+    pub tainted: Vec<i64>,
+    /// Repeats whose output didn't match the reference
+    pub invalid: Vec<i64>,
+}
+
+pub fn summarize(records: &[RunRecord]) -> Vec<Summary> {
+    group_cells(records)
+        .into_iter()
+        .filter_map(|(key, runs)| {
+            let values = runs.iter().map(|x| x.runtime_ms as f64).collect::<Vec<_>>();
+            Some(Summary {
+                key,
+                runtime: Stats::new(&values)?,
+                outliers: outliers(&values)
+                    .into_iter()
+                    .map(|i| runs[i].repeat)
+                    .collect(),
+                tainted: runs
+                    .iter()
+                    .filter(|x| x.tainted)
+                    .map(|x| x.repeat)
+                    .collect(),
+                invalid: runs
+                    .iter()
+                    .filter(|x| x.validation.as_deref() == Some("failed"))
+                    .map(|x| x.repeat)
+                    .collect(),
+            })
+        })
+        .collect()
+}
+
+/// Two-sided p-value of the Mann–Whitney U test of two samples. The exact distribution of U is used
+/// for small samples without ties, the normal approximation with tie correction otherwise
+pub fn mann_whitney(a: &[f64], b: &[f64]) -> Option<f64> {
+    let (n1, n2) = (a.len(), b.len());
+    if n1 == 0 || n2 == 0 {
+        return None;
+    }
+
+    let mut all = a
+        .iter()
+        .map(|x| (*x, true))
+        .chain(b.iter().map(|x| (*x, false)))
+        .collect::<Vec<_>>();
+    all.sort_by(|x, y| x.0.total_cmp(&y.0));
+
+    // average ranks over ties
+    let n = all.len();
+    let mut ranks = vec![0.0; n];
+    let mut tie_term = 0.0;
+    let mut i = 0;
+    while i < n {
+        let mut j = i;
+        while j + 1 < n && all[j + 1].0 == all[i].0 {
+            j += 1;
+        }
+        let t = (j - i + 1) as f64;
+        tie_term += t.powi(3) - t;
+        (i..=j).for_each(|k| ranks[k] = (i + j) as f64 / 2.0 + 1.0);
+        i = j + 1;
+    }
+
+    let r1 = all
+        .iter()
+        .zip(&ranks)
+        .filter(|(x, _)| x.1)
+        .map(|(_, r)| r)
+        .sum::<f64>();
+    let u1 = r1 - (n1 * (n1 + 1)) as f64 / 2.0;
+    let u = u1.min((n1 * n2) as f64 - u1);
+
+    if tie_term == 0.0 && n <= 20 {
+        let counts = u_distribution(n1, n2);
+        let total = counts.iter().sum::<f64>();
+        let tail = counts[..=(u as usize)].iter().sum::<f64>() / total;
+        return Some((2.0 * tail).min(1.0));
+    }
+
+    let (n1, n2, n) = (n1 as f64, n2 as f64, n as f64);
+    let mean = n1 * n2 / 2.0;
+    let var = n1 * n2 / 12.0 * ((n + 1.0) - tie_term / (n * (n - 1.0)));
+    if var <= 0.0 {
+        return Some(1.0);
+    }
+    let z = ((u - mean).abs() - 0.5).max(0.0) / var.sqrt();
+    Some((2.0 * (1.0 - normal_cdf(z))).min(1.0))
+}
+
+/// Smallest two-sided p-value the exact test can give for samples of size `n1` & `n2`, reached when
+/// every value of one sample is below the other
+pub fn min_p_value(n1: usize, n2: usize) -> f64 {
+    // 2 / C(n1 + n2, n1)
+    let arrangements = (1..=n1).fold(1.0, |c, k| c * (n2 + k) as f64 / k as f64);
+    (2.0 / arrangements).min(1.0)
+}
+
+/// Number of arrangements of two samples of size `n1` & `n2` giving each value of U
+fn u_distribution(n1: usize, n2: usize) -> Vec<f64> {
+    // f[i][j][u]: arrangements of i & j values with statistic u
+    let mut f = vec![vec![vec![0.0; n1 * n2 + 1]; n2 + 1]; n1 + 1];
+    for i in 0..=n1 {
+        for j in 0..=n2 {
+            if i == 0 || j == 0 {
+                f[i][j][0] = 1.0;
+                continue;
+            }
+            for u in 0..=(i * j) {
+                let mut c = f[i][j - 1][u];
+                if u >= j {
+                    c += f[i - 1][j][u - j];
+                }
+                f[i][j][u] = c;
+            }
+        }
+    }
+    f[n1][n2].clone()
+}
+
+/// Standard normal cumulative distribution, using the Abramowitz & Stegun approximation of erf
+fn normal_cdf(z: f64) -> f64 {
+    let x = z.abs() / std::f64::consts::SQRT_2;
+    let t = 1.0 / (1.0 + 0.3275911 * x);
+    let poly = t
+        * (0.254829592
+            + t * (-0.284496736 + t * (1.421413741 + t * (-1.453152027 + t * 1.061405429))));
+    let erf = 1.0 - poly * (-x * x).exp();
+    if z >= 0.0 {
+        (1.0 + erf) / 2.0
+    } else {
+        (1.0 - erf) / 2.0
+    }
+}
+
+/// Strong scaling of a benchmark cell, relative to the smallest cluster size its driver, dataset
+/// & algorithm ran on
+#[derive(Debug, Clone)]
+pub struct Scaling {
+    pub key: CellKey,
+    pub base_nodes: i32,
+    /// Mean runtime on the smallest cluster over the mean runtime of the cell
+    pub speedup: f64,
+    /// Speedup relative to the increase in nodes
+    pub efficiency: f64,
+    /// Experimentally determined serial fraction, undefined for the smallest cluster
+    pub karp_flatt: Option<f64>,
+}
+
+pub fn scaling(records: &[RunRecord]) -> Vec<Scaling> {
+    let mut base: Option<(CellKey, f64)> = None;
+    let mut scaling = Vec::new();
+    // cells are ordered by driver, dataset, algorithm & then nodes, so the smallest cluster is first
+    for (key, runs) in group_cells(records) {
+        let mean = runs.iter().map(|x| x.runtime_ms as f64).sum::<f64>() / runs.len() as f64;
+        if mean <= 0.0 {
+            continue;
+        }
+        let (base_key, base_mean) = match &base {
+            Some((b, m))
+                if b.driver == key.driver
+                    && b.dataset == key.dataset
+                    && b.algorithm == key.algorithm =>
+            {
+                (b.clone(), *m)
+            }
+            _ => {
+                base = Some((key.clone(), mean));
+                (key.clone(), mean)
+            }
+        };
+
+        let speedup = base_mean / mean;
+        let q = key.nodes as f64 / base_key.nodes as f64;
+        scaling.push(Scaling {
+            base_nodes: base_key.nodes,
+            speedup,
+            efficiency: speedup / q,
+            karp_flatt: (q > 1.0).then(|| (1.0 / speedup - 1.0 / q) / (1.0 - 1.0 / q)),
+            key,
+        });
+    }
+    scaling
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: f64, expected: f64, tolerance: f64) {
+        assert!(
+            (actual - expected).abs() <= tolerance,
+            "{actual} is not within {tolerance} of {expected}"
+        );
+    }
+
+    fn record(driver: &str, nodes: i32, repeat: i64, runtime_ms: i32) -> RunRecord {
+        RunRecord {
+            run_id: 1,
+            campaign: None,
+            driver: Some(driver.to_owned()),
+            dataset: "graph".to_owned(),
+            algorithm: "pr".to_owned(),
+            nodes,
+            repeat,
+            vertices: None,
+            edges: None,
+            load_ms: None,
+            runtime_ms,
+            makespan_ms: runtime_ms,
+            cpu_avg: None,
+            cpu_max: None,
+            ram_avg: None,
+            ram_max: None,
+            status: "finished".to_owned(),
+            tainted: false,
+            validation: None,
+            started_at: None,
+            finished_at: None,
+            config_hash: None,
+            speedup: None,
+            efficiency: None,
+            karp_flatt: None,
+        }
+    }
+
+    #[test]
+    fn stats_of_a_sample() {
+        let s = Stats::new(&[9.0, 2.0, 4.0, 4.0, 5.0, 4.0, 7.0, 5.0]).unwrap();
+        assert_eq!(s.n, 8);
+        assert_eq!(s.mean, 5.0);
+        assert_eq!(s.median, 4.5);
+        assert_eq!((s.min, s.max), (2.0, 9.0));
+        // sqrt(32 / 7)
+        assert_close(s.stddev, 2.138090, 1e-6);
+        assert_close(s.cv, 0.427618, 1e-6);
+        // t = 2.365 for 7 degrees of freedom
+        let (lo, hi) = s.ci.unwrap();
+        assert_close(lo, 5.0 - 1.787772, 1e-6);
+        assert_close(hi, 5.0 + 1.787772, 1e-6);
+    }
+
+    #[test]
+    fn stats_of_a_single_value() {
+        let s = Stats::new(&[3.0]).unwrap();
+        assert_eq!(
+            (s.n, s.mean, s.median, s.stddev, s.cv),
+            (1, 3.0, 3.0, 0.0, 0.0)
+        );
+        assert!(s.ci.is_none());
+        assert!(Stats::new(&[]).is_none());
+    }
+
+    #[test]
+    fn quantile_interpolates() {
+        let sorted = [1.0, 2.0, 3.0, 4.0];
+        assert_eq!(quantile(&sorted, 0.0), 1.0);
+        assert_eq!(quantile(&sorted, 0.25), 1.75);
+        assert_eq!(quantile(&sorted, 0.5), 2.5);
+        assert_eq!(quantile(&sorted, 1.0), 4.0);
+        assert_eq!(quantile(&[7.0], 0.5), 7.0);
+    }
+
+    #[test]
+    fn outliers_beyond_tukey_fences() {
+        // q1 = 11, q3 = 13, the fences are 8 & 16
+        assert_eq!(outliers(&[12.0, 100.0, 10.0, 11.0, 13.0]), [1]);
+        assert_eq!(outliers(&[12.0, 1.0, 10.0, 11.0, 13.0]), [1]);
+        assert!(outliers(&[10.0, 11.0, 12.0, 13.0, 16.0]).is_empty());
+        assert!(outliers(&[1.0, 1.0, 1.0, 1.0]).is_empty());
+        // too few values to tell
+        assert!(outliers(&[1.0, 2.0, 100.0]).is_empty());
+    }
+
+    #[test]
+    fn normal_cdf_values() {
+        assert_close(normal_cdf(0.0), 0.5, 1e-7);
+        assert_close(normal_cdf(1.96), 0.975002, 1e-6);
+        assert_close(normal_cdf(-1.0), 0.158655, 1e-6);
+        assert_close(normal_cdf(5.0), 1.0, 1e-6);
+    }
+
+    #[test]
+    fn summarize_flags_repeats() {
+        let mut records = [10, 11, 12, 13, 100]
+            .into_iter()
+            .enumerate()
+            .map(|(i, x)| record("neo4j", 1, i as i64, x))
+            .collect::<Vec<_>>();
+        records[2].tainted = true;
+        records[3].validation = Some("failed".to_owned());
+        records[4].validation = Some("passed".to_owned());
+
+        let summaries = summarize(&records);
+        assert_eq!(summaries.len(), 1);
+        let s = &summaries[0];
+        assert_eq!(s.runtime.median, 12.0);
+        assert_eq!(s.outliers, [4]);
+        assert_eq!(s.tainted, [2]);
+        assert_eq!(s.invalid, [3]);
+    }
+
+    #[test]
+    fn scaling_relative_to_the_smallest_cluster() {
+        let records = [
+            record("arango", 2, 0, 50),
+            record("arango", 4, 0, 50),
+            record("neo4j", 1, 0, 90),
+            record("neo4j", 1, 1, 110),
+            record("neo4j", 2, 0, 60),
+            record("neo4j", 4, 0, 40),
+        ];
+        let scaling = scaling(&records);
+        let metrics = scaling
+            .iter()
+            .map(|x| (x.key.driver.as_str(), x.key.nodes, x.base_nodes))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            metrics,
+            [
+                ("arango", 2, 2),
+                ("arango", 4, 2),
+                ("neo4j", 1, 1),
+                ("neo4j", 2, 1),
+                ("neo4j", 4, 1)
+            ]
+        );
+
+        // no gain from twice the nodes, everything is serial
+        assert_eq!((scaling[1].speedup, scaling[1].efficiency), (1.0, 0.5));
+        assert_close(scaling[1].karp_flatt.unwrap(), 1.0, 1e-12);
+
+        assert_eq!((scaling[2].speedup, scaling[2].efficiency), (1.0, 1.0));
+        assert!(scaling[2].karp_flatt.is_none());
+        // mean of 100 ms on a node: 100 / 60 on 2 nodes & 100 / 40 on 4
+        assert_close(scaling[3].speedup, 5.0 / 3.0, 1e-12);
+        assert_close(scaling[3].efficiency, 5.0 / 6.0, 1e-12);
+        assert_close(scaling[3].karp_flatt.unwrap(), 0.2, 1e-12);
+        assert_close(scaling[4].speedup, 2.5, 1e-12);
+        assert_close(scaling[4].efficiency, 0.625, 1e-12);
+        assert_close(scaling[4].karp_flatt.unwrap(), 0.2, 1e-12);
+    }
+
+    #[test]
+    fn scaling_skips_empty_runtimes() {
+        let records = [record("neo4j", 1, 0, 0), record("neo4j", 2, 0, 50)];
+        let scaling = scaling(&records);
+        assert_eq!(scaling.len(), 1);
+        assert_eq!(scaling[0].base_nodes, 2);
+    }
+
+    #[test]
+    fn u_distribution_counts_arrangements() {
+        assert_eq!(u_distribution(1, 1), [1.0, 1.0]);
+        assert_eq!(u_distribution(2, 2), [1.0, 1.0, 2.0, 1.0, 1.0]);
+        assert_eq!(
+            u_distribution(3, 3),
+            [1.0, 1.0, 2.0, 3.0, 3.0, 3.0, 3.0, 2.0, 1.0, 1.0]
+        );
+        // C(7, 3) arrangements
+        assert_eq!(u_distribution(3, 4).iter().sum::<f64>(), 35.0);
+    }
+
+    #[test]
+    fn mann_whitney_exact() {
+        // U = 0, 2 of the C(6, 3) arrangements are as extreme
+        assert_close(
+            mann_whitney(&[1.0, 2.0, 3.0], &[4.0, 5.0, 6.0]).unwrap(),
+            0.1,
+            1e-12,
+        );
+        assert_close(
+            mann_whitney(&[1.0, 2.0, 3.0, 4.0], &[5.0, 6.0, 7.0, 8.0]).unwrap(),
+            2.0 / 70.0,
+            1e-12,
+        );
+        // U = 1, P(U <= 1) = 2/20
+        assert_close(
+            mann_whitney(&[1.0, 2.0, 4.0], &[3.0, 5.0, 6.0]).unwrap(),
+            0.2,
+            1e-12,
+        );
+        // the test is symmetric
+        assert_close(
+            mann_whitney(&[4.0, 5.0, 6.0], &[1.0, 2.0, 3.0]).unwrap(),
+            0.1,
+            1e-12,
+        );
+        // U = 1, P(U <= 1) = 2/6
+        assert_close(
+            mann_whitney(&[1.0, 3.0], &[2.0, 4.0]).unwrap(),
+            2.0 / 3.0,
+            1e-12,
+        );
+        assert_eq!(mann_whitney(&[], &[1.0]), None);
+    }
+
+    #[test]
+    fn mann_whitney_normal_approximation() {
+        // 22 values, too many for the exact distribution: U = 0, z = 3.9399
+        let a = (1..=11).map(f64::from).collect::<Vec<_>>();
+        let b = (12..=22).map(f64::from).collect::<Vec<_>>();
+        assert_close(mann_whitney(&a, &b).unwrap(), 8.1515e-5, 1e-7);
+
+        // ties: U = 2.5, tie term 30, variance 11.2857, z = 1.4884
+        let p = mann_whitney(&[1.0, 2.0, 2.0, 3.0], &[2.0, 3.0, 4.0, 5.0]).unwrap();
+        assert_close(p, 0.136658, 1e-5);
+
+        assert_eq!(mann_whitney(&[1.0, 1.0, 1.0], &[1.0, 1.0, 1.0]), Some(1.0));
+    }
+
+    #[test]
+    fn min_p_value_of_small_samples() {
+        assert_close(min_p_value(3, 3), 0.1, 1e-12);
+        assert_close(min_p_value(4, 4), 2.0 / 70.0, 1e-12);
+        assert_close(min_p_value(2, 5), 2.0 / 21.0, 1e-12);
+        assert_eq!(min_p_value(1, 1), 1.0);
+        assert!(min_p_value(3, 3) > 0.05 && min_p_value(4, 4) < 0.05);
+    }
+}
@@ -1,3 +1,4 @@
+import os
 import toml
 import re
 
@@ -37,6 +38,9 @@ for provider in config["providers"]:
 for driver in config["drivers"]:
     workspace["workspace"]["members"].append("drivers/" + driver + "/" + driver + "-config")
     driver_base["dependencies"][driver + "-config"] = {"path": "../" + driver + "/" + driver + "-config"}
+    # drivers written in rust are built in the workspace too
+    if os.path.exists("drivers/" + driver + "/" + driver + "-driver/Cargo.toml"):
+        workspace["workspace"]["members"].append("drivers/" + driver + "/" + driver + "-driver")
 
 f = open("Cargo.toml", "w")
 toml.dump(workspace, f)
@@ -1,20 +1,65 @@
 use std::{
     collections::HashMap,
+    future::Future,
     process::Stdio,
+    sync::OnceLock,
     time::{Duration, Instant},
 };
 
 use anyhow::Result;
 use console::{style, StyledObject};
 use indicatif::{ProgressBar, ProgressStyle};
-use tokio::process::Command;
+use tokio::{process::Command, time::sleep};
+use tracing::{info, warn};
 
-use crate::exit;
+use crate::{
+    config::{RetryPolicy, RetryStep},
+    exit,
+};
 
 lazy_static::lazy_static! {
     static ref DOTS_STYLE: ProgressStyle = ProgressStyle::with_template("{spinner} {msg} {elapsed_precise}").unwrap().tick_chars("⠋⠙⠹⠸⠼⠴⠦⠧⠇⠏");
     pub static ref GREEN_TICK: StyledObject<&'static str> = style("✔").green();
-    static ref RED_CROSS: StyledObject<&'static str> = style("✗").red();
+    pub static ref RED_CROSS: StyledObject<&'static str> = style("✗").red();
+}
+
+/// Retry policy of the configuration, set by the commands that run retried steps
+static RETRY_POLICY: OnceLock<RetryPolicy> = OnceLock::new();
+
+/// Retry the steps run from now on as the policy of the configuration says, if it has one. The
+/// policy can only be set once per process
+pub fn set_retry_policy(policy: Option<RetryPolicy>) {
+    let Some(policy) = policy else { return };
+    if RETRY_POLICY.set(policy).is_err() {
+        warn!("the retry policy is already set, keeping the first one");
+    }
+}
+
+/// Run `f` until it succeeds, retrying it with an exponential backoff if the retry policy covers
+/// `step`. `what` describes the step in the log of every attempt
+pub async fn retry<T, F, Fut>(step: RetryStep, what: &str, mut f: F) -> Result<T>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T>>,
+{
+    let Some(policy) = RETRY_POLICY.get().filter(|x| x.retries(step)) else {
+        return f().await;
+    };
+    let attempts = policy.attempts();
+    let mut backoff = policy.backoff();
+    let mut attempt = 1;
+    loop {
+        info!("{what}: attempt {attempt}/{attempts}");
+        match f().await {
+            Err(err) if attempt < attempts => {
+                warn!("{what} failed on attempt {attempt}/{attempts}, retrying in {backoff}s: {err:#}");
+                sleep(Duration::from_secs(backoff)).await;
+                backoff = (backoff * 2).min(policy.max_backoff());
+                attempt += 1;
+            }
+            res => return res,
+        }
+    }
 }
 
 pub fn progress(msg: &str) -> ProgressBar {
@@ -141,3 +186,11 @@ pub fn finish_progress(
         elapsed_time_str(&dur)
     );
 }
+
+pub fn fail_progress(status_message: &str, context: &str, pb: Option<ProgressBar>) {
+    if let Some(pb) = pb {
+        pb.finish_and_clear();
+    }
+
+    println!("{} {} ({})", *RED_CROSS, status_message, context);
+}
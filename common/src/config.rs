@@ -1,4 +1,4 @@
-use std::{collections::HashMap, net::IpAddr};
+use std::{collections::HashMap, fmt::Display, net::IpAddr};
 
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
@@ -6,11 +6,15 @@ use tracing::info;
 
 use crate::exit;
 
+/// Algorithms every driver is expected to implement
+pub const ALGORITHMS: &[&str] = &["bfs", "pr", "wcc", "cdlp", "lcc", "sssp"];
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct Config {
     pub setup: SetupArgs,
     pub kubernetes: Option<KubeSetup>,
     pub benchmark: Benchmark,
+    pub retry: Option<RetryPolicy>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -24,6 +28,8 @@ pub struct PlatformConnectInfo {
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct SetupArgs {
     pub host_username: Option<String>,
+    /// Directory of the provider in `platforms`, not needed by providers that don't run from one
+    #[serde(default)]
     pub platform: String,
     pub provider: String,
     pub node_configs: Vec<usize>,
@@ -36,17 +42,45 @@ pub struct SetupArgs {
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct KubeSetup {
     pub dashboard: Option<bool>,
+    /// Namespace of every benchmark resource, `default` if not set
+    pub namespace: Option<String>,
+    /// Kubeconfig of an existing cluster to benchmark on, instead of installing k3s on the nodes
+    pub kubeconfig: Option<String>,
+    /// Context of the kubeconfig to use, its current context otherwise
+    pub context: Option<String>,
+    /// Registry the images are pushed to when using an existing cluster, eg. `registry.example.com/bench`
+    pub registry: Option<String>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct Benchmark {
+    pub campaign: Option<String>,
+    pub labels: Option<Vec<String>>,
+    pub notes: Option<String>,
     pub drivers: Vec<String>,
     pub datasets: Vec<String>,
     pub repeat: usize,
     pub algorithms: Option<Vec<String>>,
+    /// Benchmark the drivers concurrently, each on a pool of `node_configs` nodes of its own
+    pub parallel_drivers: Option<bool>,
+    pub timeouts: Option<Timeouts>,
+    /// Archive the logs of the graph platform pods of every run, along with the benchmark log
+    pub platform_logs: Option<bool>,
+    /// Compare the output of every run with the reference output of its dataset, if there is one
+    pub validate: Option<bool>,
     pub debug: Option<DebugOptions>,
 }
 
+/// Limits in seconds after which runs are marked as timed out & the benchmark moves on, no limit
+/// if not set
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct Timeouts {
+    /// Time a single algorithm may run
+    pub algorithm: Option<u64>,
+    /// Time the benchmark job of a dataset may take, loading the data & every algorithm included
+    pub dataset: Option<u64>,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct DebugOptions {
     pub bench_ttl: Option<i32>,
@@ -62,6 +96,302 @@ impl Default for DebugOptions {
     }
 }
 
+/// Retries of the infrastructure steps that can fail on transient errors, eg. a dropped ssh
+/// connection. Steps are not retried if not set
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct RetryPolicy {
+    /// Attempts of a step, the first one included, 3 if not set
+    pub attempts: Option<u32>,
+    /// Seconds to wait before the first retry, doubled after every failed attempt, 5 if not set
+    pub backoff: Option<u64>,
+    /// Upper bound of the wait between attempts in seconds, 60 if not set
+    pub max_backoff: Option<u64>,
+    /// Steps to retry, all of them if not set
+    pub steps: Option<Vec<RetryStep>>,
+}
+
+impl RetryPolicy {
+    pub fn attempts(&self) -> u32 {
+        self.attempts.unwrap_or(3)
+    }
+
+    pub fn backoff(&self) -> u64 {
+        self.backoff.unwrap_or(5)
+    }
+
+    pub fn max_backoff(&self) -> u64 {
+        self.max_backoff.unwrap_or(60)
+    }
+
+    pub fn retries(&self, step: RetryStep) -> bool {
+        self.steps.as_ref().is_none_or(|x| x.contains(&step))
+    }
+}
+
+/// Infrastructure steps a retry policy applies to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RetryStep {
+    /// Ansible playbooks setting up the cluster & installing the drivers
+    Ansible,
+    /// Copying the datasets to the cluster
+    Datasets,
+    /// Building & loading the images of the benchmark services & drivers
+    Images,
+    /// Calls to the metrics service to start & stop recording
+    Metrics,
+}
+
+/// A problem found while validating the configuration, with the TOML key it was found at
+#[derive(Debug, Clone)]
+pub struct ConfigError {
+    pub key: String,
+    pub message: String,
+}
+
+impl ConfigError {
+    pub fn new(key: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            key: key.into(),
+            message: message.into(),
+        }
+    }
+}
+
+impl Display for ConfigError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.key, self.message)
+    }
+}
+
+impl Config {
+    /// Whether the benchmarks run on an existing cluster set by `kubernetes.kubeconfig`, instead of
+    /// the k3s cluster installed on the nodes
+    pub fn existing_cluster(&self) -> bool {
+        self.kubernetes
+            .as_ref()
+            .is_some_and(|x| x.kubeconfig.is_some())
+    }
+
+    /// Namespace the benchmark resources are created in
+    pub fn namespace(&self) -> &str {
+        self.kubernetes
+            .as_ref()
+            .and_then(|x| x.namespace.as_deref())
+            .unwrap_or("default")
+    }
+
+    /// Whether the drivers are benchmarked concurrently, each on a node pool of its own
+    pub fn parallel_drivers(&self) -> bool {
+        self.benchmark.parallel_drivers.unwrap_or(false)
+    }
+
+    /// Nodes of the cluster to benchmark on `nodes` nodes per driver. With parallel drivers on a
+    /// k3s cluster, that is the master & a pool of workers for every driver
+    pub fn cluster_size(&self, nodes: usize) -> usize {
+        if self.parallel_drivers() && !self.existing_cluster() {
+            1 + nodes * self.benchmark.drivers.len()
+        } else {
+            nodes
+        }
+    }
+
+    /// Setup arguments for the providers, with the node configurations as cluster sizes
+    pub fn cluster_setup(&self) -> SetupArgs {
+        let mut setup = self.setup.clone();
+        setup.node_configs = setup
+            .node_configs
+            .iter()
+            .map(|x| self.cluster_size(*x))
+            .collect();
+        setup
+    }
+
+    /// Check the configuration for problems that would otherwise only surface during setup or
+    /// benchmarking, returning all of them at once. `providers` and `drivers` are the names
+    /// compiled into the runner
+    pub fn validate(&self, providers: &[String], drivers: &[String]) -> Vec<ConfigError> {
+        let mut errors = Vec::new();
+
+        if !providers.contains(&self.setup.provider) {
+            errors.push(ConfigError::new(
+                "setup.provider",
+                format!(
+                    "unknown provider `{}`, expected one of: {}",
+                    self.setup.provider,
+                    providers.join(", ")
+                ),
+            ));
+        }
+
+        if self.existing_cluster() {
+            let kube = self.kubernetes.as_ref().unwrap();
+            if kube.registry.as_ref().is_none_or(|x| x.trim().is_empty()) {
+                errors.push(ConfigError::new(
+                    "kubernetes.registry",
+                    "a registry to push the images to is required with kubernetes.kubeconfig",
+                ));
+            }
+            if kube.dashboard.unwrap_or(false) {
+                errors.push(ConfigError::new(
+                    "kubernetes.dashboard",
+                    "the dashboard is not installed on an existing cluster",
+                ));
+            }
+        }
+
+        if let Some(namespace) = self.kubernetes.as_ref().and_then(|x| x.namespace.as_ref()) {
+            let valid = !namespace.is_empty()
+                && namespace.len() <= 63
+                && namespace
+                    .chars()
+                    .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+                && !namespace.starts_with('-')
+                && !namespace.ends_with('-');
+            if !valid {
+                errors.push(ConfigError::new(
+                    "kubernetes.namespace",
+                    format!(
+                        "`{namespace}` is not a valid namespace, use at most 63 lowercase letters, digits & dashes"
+                    ),
+                ));
+            }
+        }
+
+        if self.setup.node_configs.is_empty() {
+            errors.push(ConfigError::new(
+                "setup.node_configs",
+                "at least one node configuration is required",
+            ));
+        }
+        for (i, n) in self.setup.node_configs.iter().enumerate() {
+            if self.parallel_drivers() {
+                if *n < 1 {
+                    errors.push(ConfigError::new(
+                        format!("setup.node_configs[{i}]"),
+                        "need at least one node in the pool of every driver",
+                    ));
+                }
+            } else if *n < 2 {
+                errors.push(ConfigError::new(
+                    format!("setup.node_configs[{i}]"),
+                    format!("need at least two nodes (a master and a worker), got {n}"),
+                ));
+            }
+            if self.setup.node_configs[..i].contains(n) {
+                errors.push(ConfigError::new(
+                    format!("setup.node_configs[{i}]"),
+                    format!("duplicate node configuration {n}"),
+                ));
+            }
+        }
+
+        if let Some(args) = &self.setup.graph_platform_args {
+            for name in args.keys() {
+                if !self.benchmark.drivers.contains(name) {
+                    errors.push(ConfigError::new(
+                        format!("setup.graph_platform_args.{name}"),
+                        format!("`{name}` is not listed in benchmark.drivers"),
+                    ));
+                }
+            }
+        }
+
+        if self.benchmark.repeat == 0 {
+            errors.push(ConfigError::new("benchmark.repeat", "must be at least 1"));
+        }
+
+        if let Some(campaign) = &self.benchmark.campaign {
+            if campaign.trim().is_empty() {
+                errors.push(ConfigError::new("benchmark.campaign", "must not be empty"));
+            }
+        }
+
+        if self.benchmark.drivers.is_empty() {
+            errors.push(ConfigError::new(
+                "benchmark.drivers",
+                "at least one driver is required",
+            ));
+        }
+        for (i, driver) in self.benchmark.drivers.iter().enumerate() {
+            if !drivers.contains(driver) {
+                errors.push(ConfigError::new(
+                    format!("benchmark.drivers[{i}]"),
+                    format!(
+                        "driver `{driver}` is not compiled in, expected one of: {} (see build.config.toml)",
+                        drivers.join(", ")
+                    ),
+                ));
+            }
+        }
+
+        if let Some(timeouts) = &self.benchmark.timeouts {
+            for (key, timeout) in [
+                ("algorithm", timeouts.algorithm),
+                ("dataset", timeouts.dataset),
+            ] {
+                if timeout == Some(0) {
+                    errors.push(ConfigError::new(
+                        format!("benchmark.timeouts.{key}"),
+                        "must be at least 1 second, remove the key for no limit",
+                    ));
+                }
+            }
+        }
+
+        if let Some(retry) = &self.retry {
+            if retry.attempts() == 0 {
+                errors.push(ConfigError::new(
+                    "retry.attempts",
+                    "must be at least 1, the first attempt included",
+                ));
+            }
+            if retry.max_backoff() < retry.backoff() {
+                errors.push(ConfigError::new(
+                    "retry.max_backoff",
+                    format!("must be at least retry.backoff ({}s)", retry.backoff()),
+                ));
+            }
+        }
+
+        if self.parallel_drivers() && self.benchmark.drivers.len() < 2 {
+            errors.push(ConfigError::new(
+                "benchmark.parallel_drivers",
+                "needs at least two drivers to benchmark in parallel",
+            ));
+        }
+
+        if self.benchmark.datasets.is_empty() {
+            errors.push(ConfigError::new(
+                "benchmark.datasets",
+                "at least one dataset is required",
+            ));
+        }
+
+        if let Some(algorithms) = &self.benchmark.algorithms {
+            if algorithms.is_empty() {
+                errors.push(ConfigError::new(
+                    "benchmark.algorithms",
+                    "remove the key to run all algorithms, or list at least one",
+                ));
+            }
+            for (i, algo) in algorithms.iter().enumerate() {
+                if !ALGORITHMS.contains(&algo.as_str()) {
+                    errors.push(ConfigError::new(
+                        format!("benchmark.algorithms[{i}]"),
+                        format!(
+                            "unknown algorithm `{algo}`, expected one of: {}",
+                            ALGORITHMS.join(", ")
+                        ),
+                    ));
+                }
+            }
+        }
+
+        errors
+    }
+}
+
 pub fn parse_config(file: &str) -> Result<Config> {
     let config = match std::fs::read_to_string(file) {
         Ok(s) => s,
@@ -77,3 +407,144 @@ pub fn parse_config(file: &str) -> Result<Config> {
     info!("config file parsed");
     Ok(config)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> Config {
+        toml::from_str(
+            r#"
+            [setup]
+            provider = "vagrant"
+            platform = "vagrant-libvirt"
+            node_configs = [2, 4]
+
+            [benchmark]
+            drivers = ["native", "neo4j"]
+            datasets = ["tiny"]
+            repeat = 3
+            "#,
+        )
+        .unwrap()
+    }
+
+    fn kubernetes() -> KubeSetup {
+        KubeSetup {
+            dashboard: None,
+            namespace: None,
+            kubeconfig: None,
+            context: None,
+            registry: None,
+        }
+    }
+
+    /// Keys of the errors found in `config`, with the providers & drivers of the default build
+    fn keys(config: &Config) -> Vec<String> {
+        let providers = ["vagrant", "static", "existing"].map(String::from);
+        let drivers = ["native", "neo4j"].map(String::from);
+        config
+            .validate(&providers, &drivers)
+            .into_iter()
+            .map(|x| x.key)
+            .collect()
+    }
+
+    #[test]
+    fn valid_config() {
+        assert!(keys(&config()).is_empty());
+    }
+
+    #[test]
+    fn unknown_provider() {
+        let mut c = config();
+        c.setup.provider = "aws".to_owned();
+        assert_eq!(keys(&c), ["setup.provider"]);
+    }
+
+    #[test]
+    fn unknown_driver() {
+        let mut c = config();
+        c.benchmark.drivers.push("tigergraph".to_owned());
+        assert_eq!(keys(&c), ["benchmark.drivers[2]"]);
+
+        c.benchmark.drivers.clear();
+        assert_eq!(keys(&c), ["benchmark.drivers"]);
+    }
+
+    #[test]
+    fn node_configs() {
+        let mut c = config();
+        c.setup.node_configs.clear();
+        assert_eq!(keys(&c), ["setup.node_configs"]);
+
+        c.setup.node_configs = vec![1, 4, 4];
+        assert_eq!(keys(&c), ["setup.node_configs[0]", "setup.node_configs[2]"]);
+
+        // a pool of a single node is enough when the drivers run in parallel
+        c.setup.node_configs = vec![1];
+        c.benchmark.parallel_drivers = Some(true);
+        assert!(keys(&c).is_empty());
+    }
+
+    #[test]
+    fn existing_cluster() {
+        let mut c = config();
+        c.setup.provider = "existing".to_owned();
+        c.setup.platform = String::new();
+        c.kubernetes = Some(KubeSetup {
+            kubeconfig: Some("kubeconfig".to_owned()),
+            registry: Some("registry.example.com/bench".to_owned()),
+            ..kubernetes()
+        });
+        assert!(keys(&c).is_empty());
+
+        c.kubernetes = Some(KubeSetup {
+            kubeconfig: Some("kubeconfig".to_owned()),
+            registry: Some(" ".to_owned()),
+            dashboard: Some(true),
+            ..kubernetes()
+        });
+        assert_eq!(keys(&c), ["kubernetes.registry", "kubernetes.dashboard"]);
+    }
+
+    #[test]
+    fn retry_bounds() {
+        let mut c = config();
+        c.retry = Some(RetryPolicy {
+            attempts: Some(0),
+            backoff: Some(30),
+            max_backoff: Some(10),
+            steps: None,
+        });
+        assert_eq!(keys(&c), ["retry.attempts", "retry.max_backoff"]);
+
+        // the default max_backoff is below a long backoff too
+        c.retry = Some(RetryPolicy {
+            attempts: Some(1),
+            backoff: Some(120),
+            max_backoff: None,
+            steps: None,
+        });
+        assert_eq!(keys(&c), ["retry.max_backoff"]);
+    }
+
+    #[test]
+    fn timeout_bounds() {
+        let mut c = config();
+        c.benchmark.timeouts = Some(Timeouts {
+            algorithm: Some(0),
+            dataset: Some(0),
+        });
+        assert_eq!(
+            keys(&c),
+            ["benchmark.timeouts.algorithm", "benchmark.timeouts.dataset"]
+        );
+
+        c.benchmark.timeouts = Some(Timeouts {
+            algorithm: Some(1),
+            dataset: None,
+        });
+        assert!(keys(&c).is_empty());
+    }
+}
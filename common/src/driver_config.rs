@@ -2,25 +2,73 @@ use std::collections::HashMap;
 
 use anyhow::Result;
 
+use crate::config::ALGORITHMS;
+
+/// Node label the runner assigns the pool of a driver with, when drivers run in parallel
+pub const POOL_LABEL: &str = "graph-benchmarks/pool";
+
+/// Nodes reserved for a single driver while drivers are benchmarked in parallel, labelled with
+/// `graph-benchmarks/pool=<name>`
+#[derive(Debug, Clone)]
+pub struct NodePool {
+    pub name: String,
+    pub nodes: Vec<String>,
+}
+
+impl NodePool {
+    /// Node selector pinning pods to the pool, as a value of helm charts
+    pub fn node_selector(&self) -> serde_yaml::Value {
+        serde_yaml::Mapping::from_iter([(POOL_LABEL.into(), self.name.as_str().into())]).into()
+    }
+}
+
 #[async_trait::async_trait]
 pub trait DriverConfig {
     /// The name of the driver
     fn name(self: &Self) -> String;
 
     /// Get host, port, or any other required platform information
-    async fn get_platform_config(&self, nodes: usize) -> Result<HashMap<String, String>>;
+    async fn get_platform_config(
+        &self,
+        nodes: usize,
+        namespace: &str,
+    ) -> Result<HashMap<String, String>>;
 
-    /// Setup config to scale pods & other resources
-    /// Returns a vector of arguments to pass with --extra-vars to ansible, eg. vec!["a=3", "b=4"]
+    /// Setup config to scale pods & other resources in `namespace`, pinned to the nodes of `pool`
+    /// if drivers run in parallel
+    /// Returns the arguments of [`DriverConfig::extra_vars`] for these nodes & options
     async fn scale_service(
         &self,
         nodes: usize,
+        namespace: &str,
+        pool: Option<&NodePool>,
         options: Option<serde_yaml::Value>,
     ) -> Result<Vec<String>>;
 
+    /// Arguments to pass with --extra-vars to ansible when installing the driver on `nodes` nodes,
+    /// eg. vec!["a=3", "b=4"]. Only derived from the arguments, nothing is written or looked up
+    fn extra_vars(
+        &self,
+        _nodes: usize,
+        _options: Option<&serde_yaml::Value>,
+    ) -> Result<Vec<String>> {
+        Ok(vec![])
+    }
+
     /// Pod ids to record metrics for
-    async fn metrics_pod_ids(&self) -> Result<Vec<String>>;
+    async fn metrics_pod_ids(&self, namespace: &str) -> Result<Vec<String>>;
 
     /// kubernetes label of the pod to check running status
-    async fn wait_for_service_ready(&self, nodes: usize) -> Result<()>;
+    async fn wait_for_service_ready(&self, nodes: usize, namespace: &str) -> Result<()>;
+
+    /// Algorithms the driver implements, the others are skipped on every dataset
+    fn algorithms(&self) -> &[&str] {
+        ALGORITHMS
+    }
+
+    /// Whether the driver writes the output of every run to the `output_dir` of its config, the
+    /// runs of other drivers are not validated
+    fn writes_output(&self) -> bool {
+        false
+    }
 }
@@ -21,6 +21,18 @@ pub struct PlatformInfo {
     pub master_ip: IpAddr,
     pub worker_ips: Vec<IpAddr>,
     pub ssh_key: String,
+    /// User to connect to the nodes as, overrides `setup.host_username`
+    pub host_username: Option<String>,
+}
+
+impl PlatformInfo {
+    /// User to connect to the nodes as, root unless set by the platform or the configuration
+    pub fn username(&self, setup_args: &SetupArgs) -> String {
+        self.host_username
+            .clone()
+            .or(setup_args.host_username.clone())
+            .unwrap_or("root".to_owned())
+    }
 }
 
 #[async_trait::async_trait]
@@ -36,4 +48,13 @@ pub trait Platform {
     async fn destroy(&self, setup_args: &SetupArgs, verbose: bool) -> Result<()>;
     /// Name of the platform provider
     fn name(&self) -> String;
+    /// Whether the provider runs from the `platforms/<platform>` directory
+    fn uses_platform_dir(&self) -> bool {
+        true
+    }
+    /// Whether the provider brings a kubernetes cluster of its own, reached through
+    /// `kubernetes.kubeconfig`, instead of nodes that k3s is installed on
+    fn provides_cluster(&self) -> bool {
+        false
+    }
 }
@@ -0,0 +1,173 @@
+use std::collections::HashMap;
+
+use anyhow::Result;
+use common::{
+    driver_config::{DriverConfig, NodePool},
+    traverse_yaml_mut,
+};
+use futures_util::{StreamExt, TryStreamExt};
+use k8s_openapi::api::core::v1::{Pod, Service};
+use kube::{
+    api::ListParams,
+    core::{ApiResource, DynamicObject, GroupVersionKind},
+    runtime::{watcher, WatchStreamExt},
+    Api, Client,
+};
+use serde_yaml::Mapping;
+use tokio::fs;
+use tracing::info;
+
+/// Name of the ArangoDeployment, also the name of the service of its coordinators
+const DEPLOYMENT: &str = "arango";
+/// Server groups of the deployment that hold or serve the graph
+const SERVER_GROUPS: [&str; 3] = ["single", "dbservers", "coordinators"];
+
+pub struct Arango;
+
+#[async_trait::async_trait]
+impl DriverConfig for Arango {
+    fn name(&self) -> String {
+        "arango".to_owned()
+    }
+
+    async fn get_platform_config(
+        &self,
+        _: usize,
+        namespace: &str,
+    ) -> Result<HashMap<String, String>> {
+        let client = Client::try_default().await?;
+        let services: Api<Service> = Api::namespaced(client, namespace);
+        let port = services
+            .get(DEPLOYMENT)
+            .await?
+            .spec
+            .and_then(|x| x.ports)
+            .and_then(|x| x.first().map(|x| x.port))
+            .unwrap_or(8529);
+        Ok(HashMap::from([
+            ("host".into(), DEPLOYMENT.into()),
+            ("port".into(), port.to_string()),
+            ("user".into(), "root".into()),
+        ]))
+    }
+
+    async fn scale_service(
+        &self,
+        nodes: usize,
+        _: &str,
+        pool: Option<&NodePool>,
+        options: Option<serde_yaml::Value>,
+    ) -> Result<Vec<String>> {
+        let f = fs::read_to_string(&format!("drivers/{}/deployment.yaml", self.name())).await?;
+        let mut deployment: serde_yaml::Value = serde_yaml::from_str(&f)?;
+
+        let options = match options {
+            Some(s) => s.as_mapping().unwrap().to_owned(),
+            None => Mapping::new(),
+        };
+
+        // a single node runs one server holding everything, the cluster mode needs 3 agents
+        let mode = if nodes == 1 { "Single" } else { "Cluster" };
+        *traverse_yaml_mut(&mut deployment, "spec.mode")?.unwrap() = mode.into();
+        *traverse_yaml_mut(&mut deployment, "spec.dbservers.count")?.unwrap() = nodes.into();
+        *traverse_yaml_mut(&mut deployment, "spec.coordinators.count")?.unwrap() = options
+            .get("coordinators")
+            .cloned()
+            .unwrap_or_else(|| nodes.into());
+
+        for group in SERVER_GROUPS {
+            if options.contains_key("cpu") {
+                *traverse_yaml_mut(
+                    &mut deployment,
+                    &format!("spec.{group}.resources.requests.cpu"),
+                )?
+                .unwrap() = options.get("cpu").unwrap().clone();
+            }
+
+            if options.contains_key("memory") {
+                *traverse_yaml_mut(
+                    &mut deployment,
+                    &format!("spec.{group}.resources.requests.memory"),
+                )?
+                .unwrap() = options.get("memory").unwrap().clone();
+            }
+        }
+
+        if options.contains_key("storage") {
+            for group in ["single", "dbservers"] {
+                *traverse_yaml_mut(
+                    &mut deployment,
+                    &format!("spec.{group}.volumeClaimTemplate.spec.resources.requests.storage"),
+                )?
+                .unwrap() = options.get("storage").unwrap().clone();
+            }
+        }
+
+        if let Some(pool) = pool {
+            for group in ["single", "agents", "dbservers", "coordinators"] {
+                deployment["spec"][group]["nodeSelector"] = pool.node_selector();
+            }
+        }
+
+        fs::create_dir_all(format!("drivers/{}/tmp", self.name())).await?;
+        fs::write(
+            format!("drivers/{}/tmp/deployment.yaml", self.name()),
+            serde_yaml::to_string(&deployment)?,
+        )
+        .await?;
+        Ok(vec![])
+    }
+
+    async fn metrics_pod_ids(&self, namespace: &str) -> Result<Vec<String>> {
+        let client = Client::try_default().await?;
+        let pods: Api<Pod> = Api::namespaced(client, namespace);
+        let pods = pods
+            .list(&ListParams::default().labels(&format!(
+                "arango_deployment={DEPLOYMENT},role in (single,dbserver,coordinator)"
+            )))
+            .await?;
+        Ok(pods
+            .items
+            .into_iter()
+            .map(|x| x.metadata.name.unwrap())
+            .collect())
+    }
+
+    async fn wait_for_service_ready(&self, _: usize, namespace: &str) -> Result<()> {
+        let client = Client::try_default().await?;
+        let resource = ApiResource::from_gvk(&GroupVersionKind::gvk(
+            "database.arangodb.com",
+            "v1",
+            "ArangoDeployment",
+        ));
+        let deployments: Api<DynamicObject> = Api::namespaced_with(client, namespace, &resource);
+        let wc = watcher::Config::default().fields(&format!("metadata.name={DEPLOYMENT}"));
+
+        // the operator sets the ready condition once every server of the deployment is up
+        let status_check = |deployment: DynamicObject| {
+            deployment.data["status"]["conditions"]
+                .as_array()
+                .is_some_and(|x| {
+                    x.iter()
+                        .any(|x| x["type"] == "Ready" && x["status"] == "True")
+                })
+        };
+
+        let mut res = watcher(deployments, wc)
+            .applied_objects()
+            .default_backoff()
+            .boxed();
+        while let Ok(Some(d)) = res.try_next().await {
+            info!("got status update {}", d.metadata.name.as_ref().unwrap());
+            if status_check(d) {
+                break;
+            }
+        }
+        Ok(())
+    }
+
+    // pregel has no local clustering coefficient
+    fn algorithms(&self) -> &[&str] {
+        &["bfs", "pr", "wcc", "cdlp", "sssp"]
+    }
+}
@@ -13,7 +13,7 @@ async function check_table(client) {
   // create a table
   if (!tableExists) {
     const create_table_query =
-      "CREATE TABLE gn_test(id INTEGER, algo VARCHAR(256), dataset VARCHAR(256), type VARCHAR(256), time INTEGER, vertex INTEGER, edge INTEGER, nodes INTEGER)";
+      "CREATE TABLE gn_test(id INTEGER, algo VARCHAR(256), dataset VARCHAR(256), type VARCHAR(256), time INTEGER, vertex INTEGER, edge INTEGER, nodes INTEGER, seq BIGSERIAL)";
     await client.query(create_table_query);
   }
 }
@@ -42,126 +42,97 @@ async function log_metrics_sql(
     nodes,
   ]);
 }
-
-async function get_vertex_edge_count() {}
-
 // can only be run after data has been loaded
 function run_arangosh(arango_host, arango_port, arango_user, command) {
-  // creating graph
   const endpoint = `--server.endpoint tcp://${arango_host}:${arango_port}`;
   const user = `--server.username ${arango_user}`;
   const auth = `--server.authentication false`;
   const password = `--server.password ""`
   const com = `arangosh ${endpoint} ${user} ${auth} ${password} ${command}`
-  //console.log(com)
   child_process.execSync(com);
 }
 
 function get_execution_result(output_file) {
   const resf = fs.readFileSync(output_file, "utf8");
   // regular expression match
-  const regexPattern = /!===[\s\S]*?===!/;
+  const regexPattern = /!===([^#][\s\S]*?)===!/;
   const match = resf.match(regexPattern);
-  const match_str = match[0];
+  if (match === null) {
+    throw new Error(`no result in the arangosh output: ${resf}`);
+  }
 
   const regexPattern2 = /!===#(.*?)#===!/;
   const match2 = resf.match(regexPattern2);
-  let ret2 = ""
-
-  if (match2 !== null){
-        const match_str2 = match2[0];
-        ret2 = match_str2.replace("!===#", "").replace("#===!", "");
-  } else {
-        ret2 =  ""
-  }
-  const ret1 = match_str.replace("!===", "").replace("===!", ""); // solution
-  return [ret1, ret2];
-}
-
-function remove_execution_code(result_code_file, code) {
-  const rf = fs.readFileSync(result_code_file, "utf8");
-  const new_rf = rf.replace(code, "!!!----!!!");
-  fs.writeFileSync(result_code_file, new_rf);
+  const ret2 = match2 === null ? "" : match2[1];
+  return [match[1], ret2];
 }
 
-function add_execution_code(result_code_file, code) {
-  const rf = fs.readFileSync(result_code_file, "utf8");
-  const new_rf = rf.replace("!!!----!!!", code);
-  fs.writeFileSync(result_code_file, new_rf);
+// run an arangosh script, after replacing its `!!!<name>!!!` placeholders
+function run_script(arango, script, output_file, replacements = {}) {
+  let code = fs.readFileSync(script, "utf8");
+  for (const [name, value] of Object.entries(replacements)) {
+    code = code.replaceAll(`!!!${name}!!!`, value);
+  }
+  const script_file = `/tmp/${script}`;
+  fs.writeFileSync(script_file, code);
+  run_arangosh(arango.host, arango.port, arango.user, `< ${script_file} > ${output_file}`);
+  return get_execution_result(output_file);
 }
 
-async function wait_until_exec_result(output_file) {
-  while (True) {
-    await new Promise((resolve) => setTimeout(resolve, 1000));
-    exec_result = get_execution_result(output_file);
-    if (exec_result[0] === "done") {
-      return exec_result[1];
+// wait for a pregel execution to end, returns its runtime in seconds
+async function wait_for_pregel(arango, execution, output_file) {
+  while (true) {
+    const [state, runtime] = run_script(arango, "pregel_result.js", output_file, { "----": execution });
+    if (state === "done") {
+      const dur = parseFloat(runtime);
+      if (isNaN(dur)) {
+        throw new Error(`invalid runtime ${runtime} of pregel execution ${execution}`);
+      }
+      return dur;
+    }
+    if (["canceled", "fatal error", "in error"].includes(state)) {
+      throw new Error(`pregel execution ${execution} ended as ${state}`);
     }
+    await new Promise((resolve) => setTimeout(resolve, 1000));
   }
 }
 
-async function get_duration(arango_host, arango_port, arango_user, res_code_file, output_file){
-  // get run_id
-  const code = get_execution_result(output_file)[0];
-  // insert run_id into script
-  add_execution_code(res_code_file, code);
-  const res_command = `< pregel_result.js > ${output_file}`
-  run_arangosh(arango_host, arango_port, arango_user, res_command)
-  const dur = await wait_until_exec_result(output_file);
-  remove_execution_code(res_code_file, code);
-  return dur;
+async function run_pregel(arango, script, output_file, replacements = {}) {
+  const [execution] = run_script(arango, script, output_file, replacements);
+  return await wait_for_pregel(arango, execution, output_file);
 }
 
-async function bfs() {}
+// arangodb's pregel sssp counts the edges from the source, the depth bfs computes
+async function bfs(arango, dataset, output_file) {
+  return await run_pregel(arango, "pregel_sssp.js", output_file, { source: dataset.start_vertex });
+}
 
-async function pr(
-  arango_host,
-  arango_port,
-  arango_user,
-  res_code_file,
-  output_file
-) {
-  const command = `< pregel_pr.js > ${output_file}`;
-  run_arangosh(arango_host, arango_port, arango_user, command);
-  return await get_duration(arango_host, arango_port, arango_user, res_code_file, output_file)
-  }
+async function pr(arango, dataset, output_file) {
+  return await run_pregel(arango, "pregel_pr.js", output_file);
+}
 
-async function wcc(
-  arango_host,
-  arango_port,
-  arango_user,
-  res_code_file,
-  output_file
-) {
-  const command = `< pregel_wcc.js > ${output_file}`;
-  run_arangosh(arango_host, arango_port, arango_user, command);
-  return await get_duration(arango_host, arango_port, arango_user, res_code_file, output_file)
-  }
+async function wcc(arango, dataset, output_file) {
+  return await run_pregel(arango, "pregel_wcc.js", output_file);
+}
 
-async function cdlp(
-  arango_host,
-  arango_port,
-  arango_user,
-  res_code_file,
-  output_file
-) {
-  const command = `< pregel_cdlp.js > ${output_file}`;
-  run_arangosh(arango_host, arango_port, arango_user, command);
-  return await get_duration(arango_host, arango_port, arango_user, res_code_file, output_file)
+async function cdlp(arango, dataset, output_file) {
+  return await run_pregel(arango, "pregel_cdlp.js", output_file);
 }
 
-async function lcc() {}
+// pregel ignores the edge weights, the distances are hop counts
+async function sssp(arango, dataset, output_file) {
+  return await run_pregel(arango, "pregel_sssp.js", output_file, { source: dataset.start_vertex });
+}
 
-async function sssp(
-  arango_host,
-  arango_port,
-  arango_user,
-  res_code_file,
-  output_file,
-) {
-  const command = `< pregel_sssp.js > ${output_file}`;
-  run_arangosh(arango_host, arango_port, arango_user, command);
-  return await get_duration(arango_host, arango_port, arango_user, res_code_file, output_file)
+// import the dataset & create the graph, returns the loading time in ms
+function load_data(arango, dataset) {
+  const start = process.hrtime.bigint();
+  const endpoint = `tcp://${arango.host}:${arango.port}`;
+  child_process.execSync(
+    `sh arango_load_data.sh ${endpoint} ${dataset.vertex} ${dataset.edges} ${dataset.weights}`,
+    { stdio: "inherit" },
+  );
+  return Number((process.hrtime.bigint() - start) / 1000000n);
 }
 
 async function main() {
@@ -183,9 +154,11 @@ async function main() {
   log_file = config["config"]["log_file"];
   lf = fs.openSync(log_file, "w+");
 
-  arango_host = config["platform"]["host"];
-  arango_port = config["platform"]["port"];
-  arango_user = config["platform"]["user"];
+  const arango = {
+    host: config["platform"]["host"],
+    port: config["platform"]["port"],
+    user: config["platform"]["user"],
+  };
 
   pg_host = config["postgres"]["host"];
   pg_db = config["postgres"]["db"];
@@ -193,9 +166,15 @@ async function main() {
   user_ps = config["postgres"]["ps"];
   pg_user = config["postgres"]["user"];
 
-  dataset = config["dataset"]["name"];
-  vertex_file = config["dataset"]["vertex"];
-  edge_file = config["dataset"]["edges"];
+  const dataset = config["dataset"];
+
+  func_d = { bfs, pr, wcc, cdlp, sssp };
+  for (const algo of algos) {
+    if (!(algo in func_d)) {
+      fs.writeFileSync(lf, `Error: arangodb doesn't implement ${algo}\n`);
+      process.exit(1);
+    }
+  }
 
   // connect to postgres
   const pg_URI = `postgresql://${pg_user}:${user_ps}@${pg_host}:${pg_port}/${pg_db}`;
@@ -204,46 +183,43 @@ async function main() {
   try {
     await client.connect();
   } catch {
-    fs.writeFileSync(lf, "Error: could not connect to postgres");
-    process.exit();
+    fs.writeFileSync(lf, "Error: could not connect to postgres\n");
+    process.exit(1);
   }
-  // connect to arango
 
   await check_table(client);
 
-  /*
-for (entry of id_algos) {
-    const tid = entry[0];
-    const talgo = entry[1];
-    await log_metrics_sql(
-      client,
-      tid,
-      talgo,
-      dataset,
-      "loading",
-      dur,
-      vertex,
-      edge,
-      nodes,
-    );
-  }
-*/
-  func_d = { bfs, pr, wcc, cdlp, lcc, sssp };
-  const output_file = "arangosh_output.txt"
-  const result_file = "pregel_result.js"
-
-  // create a graph
-  create_graph_command = "< pregel_create_graph.js"
-  run_arangosh(arango_host, arango_port, arango_user, create_graph_command);
-
-  for (entry of id_algos) {
-    const tid = entry[0];
-    const algo = entry[1];
-    await fetch("http://notifier:8080/starting")
-    dur = func_d[algo](arango_host, arango_port, arango_user, result_file, output_file);
-    dur = parseInt(parseFloat(dur) * 1000)
-    await fetch("http://notifier:8080/stopping")
-    log_metrics_sql(client, tid, algo, dataset, "runtime", dur, 0, 0, nodes)
+  const output_file = "/tmp/arangosh_output.txt"
+  try {
+    let loading = null;
+    if (config["load_data"]) {
+      loading = load_data(arango, dataset);
+    }
+    // create the graph, if it doesn't exist yet
+    const [vertex, edge] = run_script(arango, "pregel_create_graph.js", output_file).map((x) => parseInt(x));
+    fs.writeSync(lf, `${dataset.name} has ${vertex} vertices & ${edge} edges\n`);
+
+    if (loading !== null) {
+      for (const [tid, algo] of id_algos) {
+        await log_metrics_sql(client, tid, algo, dataset.name, "loading", loading, vertex, edge, nodes);
+      }
+    }
+
+    for (const [tid, algo] of id_algos) {
+      await fetch(`http://notifier:8080/starting?id=${tid}`, { method: "POST" })
+      const dur = await func_d[algo](arango, dataset, output_file);
+      await fetch(`http://notifier:8080/stopping?id=${tid}`, { method: "POST" })
+      await log_metrics_sql(client, tid, algo, dataset.name, "runtime", Math.round(dur * 1000), vertex, edge, nodes)
+    }
+
+    if (config["drop_data"]) {
+      run_script(arango, "pregel_drop_graph.js", output_file);
+    }
+  } catch (err) {
+    fs.writeSync(lf, `Error: ${err.stack}\n`);
+    fs.closeSync(lf);
+    await client.end();
+    process.exit(1);
   }
 
   fs.closeSync(lf);
@@ -1,2 +1,24 @@
-arangoimport --file "/attached/ar_vertex.csv" --type csv --collection "vertex" --translate "vertex=_key" --create-collection true --server.password ""
-arangoimport --file "/attached/ar_edges.csv" --type csv --collection "edges" --create-collection true --create-collection-type edge --translate "src=_from" --translate "dst=_to" --server.password ""
+#!/bin/sh
+# usage: arango_load_data.sh <endpoint> <vertex file> <edge file> <weights>
+# imports the space separated vertex & edge files of a dataset, replacing the collections
+set -e
+endpoint="$1"
+vertex_file="$2"
+edge_file="$3"
+weights="$4"
+
+echo "id" > /tmp/vertex_headers.csv
+if [ "$weights" = "true" ]; then
+  echo "src dst weight" > /tmp/edge_headers.csv
+else
+  echo "src dst" > /tmp/edge_headers.csv
+fi
+
+arangoimport --server.endpoint "$endpoint" --server.authentication false --server.password "" \
+  --file "$vertex_file" --type csv --separator " " --headers-file /tmp/vertex_headers.csv \
+  --collection "vertex" --create-collection true --overwrite true --translate "id=_key"
+arangoimport --server.endpoint "$endpoint" --server.authentication false --server.password "" \
+  --file "$edge_file" --type csv --separator " " --headers-file /tmp/edge_headers.csv \
+  --collection "edges" --create-collection true --create-collection-type edge --overwrite true \
+  --translate "src=_from" --translate "dst=_to" \
+  --from-collection-prefix "vertex" --to-collection-prefix "vertex"
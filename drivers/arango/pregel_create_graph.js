@@ -1,13 +1,16 @@
 // has to be run from arangosh
 
 var graph_module = require("@arangodb/general-graph");
-var graph = graph_module._create("testGraph");
+if (!graph_module._exists("testGraph")) {
+  var graph = graph_module._create("testGraph");
 
-// adding vertex collections
-graph._addVertexCollection("vertex");
-graph = graph_module._graph("testGraph");
+  // adding vertex collections
+  graph._addVertexCollection("vertex");
+  graph = graph_module._graph("testGraph");
 
-// edge definition
-var rel = graph_module._relation("edges", ["vertex"], ["vertex"]);
-graph._extendEdgeDefinitions(rel);
-graph = graph_module._graph("testGraph");
+  // edge definition
+  var rel = graph_module._relation("edges", ["vertex"], ["vertex"]);
+  graph._extendEdgeDefinitions(rel);
+}
+console.log(`\n!===${db.vertex.count()}===!\n`)
+console.log(`\n!===#${db.edges.count()}#===!\n`)
@@ -0,0 +1,8 @@
+// has to be run from arangosh
+
+var graph_module = require("@arangodb/general-graph");
+if (graph_module._exists("testGraph")) {
+  // drops the vertex & edge collections along with the graph
+  graph_module._drop("testGraph", true);
+}
+console.log(`\n!===done===!\n`)
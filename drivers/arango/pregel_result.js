@@ -1,5 +1,6 @@
 
 var pregel = require("@arangodb/pregel")
-const execution = "!!!----!!!"  // replace this with the execution time
-console.log(`\n!===${pregel.status(execution)["state"]}===!\n`)
-console.log(`\n!===#${pregel.status(execution)["totalRuntime"]}===#!\n`)
+const execution = "!!!----!!!"  // replace this with the execution id
+const status = pregel.status(execution)
+console.log(`\n!===${status["state"]}===!\n`)
+console.log(`\n!===#${status["totalRuntime"]}#===!\n`)
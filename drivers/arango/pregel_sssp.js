@@ -1,5 +1,5 @@
 
 var pregel = require("@arangodb/pregel");
-var params = {source: "vertex/1"};
+var params = {source: "vertex/!!!source!!!", resultField: "distance"};
 var execution = pregel.start("sssp", "testGraph", params);
 console.log(`\n!===${execution}===!\n`)
@@ -1,7 +1,7 @@
 use std::collections::HashMap;
 
 use anyhow::Result;
-use common::driver_config::DriverConfig;
+use common::driver_config::{DriverConfig, NodePool};
 use common::traverse_yaml_mut;
 use futures_util::{StreamExt, TryStreamExt};
 use k8s_openapi::api::core::v1::{Pod, Service};
@@ -22,9 +22,13 @@ impl DriverConfig for Graphscope {
         "graphscope".to_owned()
     }
 
-    async fn get_platform_config(&self, _: usize) -> Result<HashMap<String, String>> {
+    async fn get_platform_config(
+        &self,
+        _: usize,
+        namespace: &str,
+    ) -> Result<HashMap<String, String>> {
         let client = Client::try_default().await?;
-        let services: Api<Service> = Api::default_namespaced(client);
+        let services: Api<Service> = Api::namespaced(client, namespace);
         let coordinator = services.get("coordinator-service-graphscope").await?;
         Ok(HashMap::from([
             ("host".into(), "coordinator-service-graphscope".into()),
@@ -38,6 +42,8 @@ impl DriverConfig for Graphscope {
     async fn scale_service(
         &self,
         nodes: usize,
+        _: &str,
+        pool: Option<&NodePool>,
         options: Option<serde_yaml::Value>,
     ) -> Result<Vec<String>> {
         let values_file = format!("drivers/{}/values.yaml", self.name());
@@ -51,6 +57,14 @@ impl DriverConfig for Graphscope {
 
         let engines = data.get_mut("engines").unwrap();
         *engines.get_mut("num_workers").unwrap() = nodes.into();
+        // the values are rewritten in place, so a pool of an earlier run has to be removed
+        for key in ["engines", "coordinator", "vineyard"] {
+            let section = data[key].as_mapping_mut().unwrap();
+            match pool {
+                Some(pool) => _ = section.insert("nodeSelector".into(), pool.node_selector()),
+                None => _ = section.remove("nodeSelector"),
+            }
+        }
 
         if options.contains_key("cpu") {
             let cpu = options.get("cpu").unwrap().clone();
@@ -80,9 +94,9 @@ impl DriverConfig for Graphscope {
         Ok(vec![])
     }
 
-    async fn metrics_pod_ids(&self) -> Result<Vec<String>> {
+    async fn metrics_pod_ids(&self, namespace: &str) -> Result<Vec<String>> {
         let client = Client::try_default().await?;
-        let pods: Api<Pod> = Api::default_namespaced(client);
+        let pods: Api<Pod> = Api::namespaced(client, namespace);
         let pods = pods
             .list(&ListParams::default().labels("app.kubernetes.io/name=graphscope"))
             .await?;
@@ -93,9 +107,9 @@ impl DriverConfig for Graphscope {
             .collect())
     }
 
-    async fn wait_for_service_ready(&self, _: usize) -> Result<()> {
+    async fn wait_for_service_ready(&self, _: usize, namespace: &str) -> Result<()> {
         let client = Client::try_default().await?;
-        let ss: Api<Pod> = Api::default_namespaced(client);
+        let ss: Api<Pod> = Api::namespaced(client, namespace);
         let wc = watcher::Config::default().labels("graphscope.components=coordinator");
 
         let status_check = |pod: Pod| {
@@ -25,7 +25,7 @@ def check_table(conn: psycopg.Connection) -> None:
 
     if not ret.fetchone()[0]:
         query = sql.SQL(
-            "CREATE TABLE gn_test(id INTEGER, algo VARCHAR(256), dataset VARCHAR(256), type VARCHAR(256), time INTEGER, vertex INTEGER, edge INTEGER, nodes INTEGER)"
+            "CREATE TABLE gn_test(id INTEGER, algo VARCHAR(256), dataset VARCHAR(256), type VARCHAR(256), time INTEGER, vertex INTEGER, edge INTEGER, nodes INTEGER, seq BIGSERIAL)"
         )
         cur.execute(query)
 
@@ -68,11 +68,17 @@ def graph_vertex_edge_count(sess: gs.Session, g: Graph | GraphDAGNode):
 JOB_NAME = "bench-graphscope-hdfs-loader"
 
 
+# namespace the benchmark job runs in, its loader job & the nfs server are in the same one
+def namespace() -> str:
+    with open("/var/run/secrets/kubernetes.io/serviceaccount/namespace") as f:
+        return f.read().strip()
+
+
 def wait_for_job_completion(api_instance):
     job_completed = False
     while not job_completed:
         api_response = api_instance.read_namespaced_job_status(
-            name=JOB_NAME, namespace="default"
+            name=JOB_NAME, namespace=namespace()
         )
         if (
             api_response.status.succeeded is not None
@@ -92,7 +98,7 @@ def load_data(configs, sess: gs.Session, vertex_file: str, edge_file: str):
     KubeConfig.load_incluster_config()
 
     api = client.CoreV1Api()
-    service = api.read_namespaced_service(name="nfs-service", namespace="default")
+    service = api.read_namespaced_service(name="nfs-service", namespace=namespace())
     nfs_ip = service.spec.cluster_ip
 
     container = client.V1Container(
@@ -137,7 +143,7 @@ def load_data(configs, sess: gs.Session, vertex_file: str, edge_file: str):
     global lf
     lf.write("starting copy job")
     api_response = client.BatchV1Api().create_namespaced_job(
-        body=job, namespace="default"
+        body=job, namespace=namespace()
     )
     wait_for_job_completion(client.BatchV1Api())
     lf.write("done copying datasets to hdfs")
@@ -303,9 +309,9 @@ def main():
 
     for entry in id_algos:
         lf.write("starting " + entry[1] + " with id " + str(entry[0]))
-        requests.post("http://notifier:8080/starting")
+        requests.post("http://notifier:8080/starting", params={"id": entry[0]})
         dur = func_d[entry[1]](config, g)
-        requests.post("http://notifier:8080/stopping")
+        requests.post("http://notifier:8080/stopping", params={"id": entry[0]})
 
         if dur > 0:
             log_metrics_sql(
@@ -12,8 +12,8 @@ def check_table(conn: psycopg.Connection)->None:
     ret = cur.execute(query)
     
     if not ret.fetchone()[0]:
-        query = sql.SQL("CREATE TABLE gn_test(id INTEGER, algo VARCHAR(256), dataset VARCHAR(256), type VARCHAR(256), time INTEGER, vertex INTEGER, edge INTEGER)")  
-    cur.execute(query)
+        query = sql.SQL("CREATE TABLE gn_test(id INTEGER, algo VARCHAR(256), dataset VARCHAR(256), type VARCHAR(256), time INTEGER, vertex INTEGER, edge INTEGER, nodes INTEGER, seq BIGSERIAL)")  
+        cur.execute(query)
     conn.commit()       
     cur.close()
 
@@ -27,14 +27,14 @@ def graph_edge_count(url:str)->int:
     r = requests.get(f"{url}?gremlin={query}")
     return 0
 
-def log_metrics_sql(conn: psycopg.Connection, log_id:int, algo:str, dataset:str, type_:str, time:float, vertex:int, edge:int)->None:
-    columns = ["id", "algo", "dataset", "type", "time", "vertex", "edge"]
+def log_metrics_sql(conn: psycopg.Connection, log_id:int, algo:str, dataset:str, type_:str, time:float, vertex:int, edge:int, nodes:int)->None:
+    columns = ["id", "algo", "dataset", "type", "time", "vertex", "edge", "nodes"]
     cur = conn.cursor()
     query = sql.SQL("INSERT INTO gn_test ({}) VALUES ({})").format(
             sql.SQL(', ').join(map(sql.Identifier, columns)),
             sql.SQL(', ').join(sql.Placeholder() * len(columns)))
 
-    cur.execute(query, (log_id, algo, dataset, type_, time, vertex, edge))
+    cur.execute(query, (log_id, algo, dataset, type_, time, vertex, edge, nodes))
     conn.commit()
     cur.close()    
 
@@ -77,8 +77,10 @@ def main():
     with open(config_yml, 'r') as yml_file:
         configs = yaml.safe_load(yml_file)
      
-    id_ = int(configs["config"]["id"])
-    algo = configs["config"]["algo"]
+    ids = [int(x.strip()) for x in configs["config"]["ids"].split(",")]
+    algos = [x.strip() for x in configs["config"]["algos"].split(",")]
+    id_algos = list(zip(ids, algos))
+    nodes = configs["config"]["nodes"]
 
     log_file = configs["config"]["log_file"]
     lf = open(log_file, "w+")
@@ -113,14 +115,17 @@ def main():
 
     func_d = {'bfs': bfs, 'pr':pr, 'wcc':wcc, 'cdlp':cdlp, 'lcc':lcc, 'sssp':sssp}
 
-    start_time = time.clock_gettime_ns(time.CLOCK_MONOTONIC)
-    func_d[algo](url)
-    end_time = time.clock_gettime_ns(time.CLOCK_MONOTONIC)
-
-    duration = end_time - start_time
-    duration = duration // 1000000
-    print(id_, algo, dataset, "runtime", duration, vertex, edge)
-    log_metrics_sql(conn, id_, algo, dataset, "runtime", duration, vertex, edge)
+    for entry in id_algos:
+        requests.post("http://notifier:8080/starting", params={"id": entry[0]})
+        start_time = time.clock_gettime_ns(time.CLOCK_MONOTONIC)
+        func_d[entry[1]](url)
+        end_time = time.clock_gettime_ns(time.CLOCK_MONOTONIC)
+        requests.post("http://notifier:8080/stopping", params={"id": entry[0]})
+
+        duration = end_time - start_time
+        duration = duration // 1000000
+        print(entry[0], entry[1], dataset, "runtime", duration, vertex, edge)
+        log_metrics_sql(conn, entry[0], entry[1], dataset, "runtime", duration, vertex, edge, nodes)
 
     lf.close()
     conn.close()
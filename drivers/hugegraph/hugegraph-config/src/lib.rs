@@ -0,0 +1,138 @@
+use std::collections::HashMap;
+
+use anyhow::Result;
+use common::{
+    driver_config::{DriverConfig, NodePool},
+    traverse_yaml_mut,
+};
+use futures_util::{StreamExt, TryStreamExt};
+use k8s_openapi::api::{
+    apps::v1::StatefulSet,
+    core::v1::{Pod, Service},
+};
+use kube::{
+    api::ListParams,
+    runtime::{watcher, WatchStreamExt},
+    Api, Client,
+};
+use serde_yaml::Mapping;
+use tokio::fs;
+use tracing::info;
+
+const LABELS: &str = "app=hugegraph";
+
+pub struct Hugegraph;
+
+#[async_trait::async_trait]
+impl DriverConfig for Hugegraph {
+    fn name(&self) -> String {
+        "hugegraph".to_owned()
+    }
+
+    async fn get_platform_config(
+        &self,
+        _: usize,
+        namespace: &str,
+    ) -> Result<HashMap<String, String>> {
+        let client = Client::try_default().await?;
+        let services: Api<Service> = Api::namespaced(client, namespace);
+        let server = services.get("hugegraph").await?;
+        Ok(HashMap::from([
+            ("host".into(), "hugegraph".into()),
+            (
+                "port".into(),
+                server.spec.unwrap().ports.unwrap()[0].port.to_string(),
+            ),
+            ("graph".into(), "hugegraph".into()),
+        ]))
+    }
+
+    async fn scale_service(
+        &self,
+        _: usize,
+        _: &str,
+        pool: Option<&NodePool>,
+        options: Option<serde_yaml::Value>,
+    ) -> Result<Vec<String>> {
+        let f = fs::read_to_string(format!("drivers/{}/hugegraph.yaml", self.name())).await?;
+        let mut server: serde_yaml::Value = serde_yaml::from_str(&f)?;
+
+        let options = match options {
+            Some(s) => s.as_mapping().unwrap().to_owned(),
+            None => Mapping::new(),
+        };
+
+        // the server keeps the graph in an embedded rocksdb, so there is a single one whatever the
+        // number of nodes
+        *traverse_yaml_mut(&mut server, "spec.replicas")?.unwrap() = 1.into();
+
+        if options.contains_key("cpu") {
+            *traverse_yaml_mut(
+                &mut server,
+                "spec.template.spec.containers.0.resources.requests.cpu",
+            )?
+            .unwrap() = options.get("cpu").unwrap().clone();
+        }
+
+        if options.contains_key("memory") {
+            *traverse_yaml_mut(
+                &mut server,
+                "spec.template.spec.containers.0.resources.requests.memory",
+            )?
+            .unwrap() = options.get("memory").unwrap().clone();
+        }
+
+        if options.contains_key("storage") {
+            *traverse_yaml_mut(
+                &mut server,
+                "spec.volumeClaimTemplates.0.spec.resources.requests.storage",
+            )?
+            .unwrap() = options.get("storage").unwrap().clone();
+        }
+
+        if let Some(pool) = pool {
+            server["spec"]["template"]["spec"]["nodeSelector"] = pool.node_selector();
+        }
+
+        fs::create_dir_all(format!("drivers/{}/tmp", self.name())).await?;
+        fs::write(
+            format!("drivers/{}/tmp/hugegraph.yaml", self.name()),
+            serde_yaml::to_string(&server)?,
+        )
+        .await?;
+        Ok(vec![])
+    }
+
+    async fn metrics_pod_ids(&self, namespace: &str) -> Result<Vec<String>> {
+        let client = Client::try_default().await?;
+        let pods: Api<Pod> = Api::namespaced(client, namespace);
+        let pods = pods.list(&ListParams::default().labels(LABELS)).await?;
+        Ok(pods
+            .items
+            .into_iter()
+            .map(|x| x.metadata.name.unwrap())
+            .collect())
+    }
+
+    async fn wait_for_service_ready(&self, _: usize, namespace: &str) -> Result<()> {
+        let client = Client::try_default().await?;
+        let ss: Api<StatefulSet> = Api::namespaced(client, namespace);
+        let wc = watcher::Config::default().labels(LABELS);
+
+        let status_check = |ss: StatefulSet| {
+            if let Some(status) = ss.status {
+                return status.replicas > 0 && status.ready_replicas == Some(status.replicas);
+            }
+            false
+        };
+
+        let mut res = watcher(ss, wc).applied_objects().default_backoff().boxed();
+        while let Ok(Some(s)) = res.try_next().await {
+            info!("got status update {}", s.metadata.name.as_ref().unwrap());
+            if status_check(s) {
+                break;
+            }
+        }
+        Ok(())
+    }
+}
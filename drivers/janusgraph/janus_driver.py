@@ -3,6 +3,7 @@ import sys
 import traceback
 from gremlin_python.process.traversal import PageRank
 import psycopg
+import requests
 import psycopg.sql as sql
 import yaml
 import time
@@ -19,7 +20,7 @@ def check_table(conn: psycopg.Connection)->None:
     ret = cur.execute(query)
     
     if not ret.fetchone()[0]:
-        query = sql.SQL("CREATE TABLE gn_test(id INTEGER, algo VARCHAR(256), dataset VARCHAR(256), type VARCHAR(256), time INTEGER, vertex INTEGER, edge INTEGER)")  
+        query = sql.SQL("CREATE TABLE gn_test(id INTEGER, algo VARCHAR(256), dataset VARCHAR(256), type VARCHAR(256), time INTEGER, vertex INTEGER, edge INTEGER, nodes INTEGER, seq BIGSERIAL)")  
         cur.execute(query)
  
     conn.commit()       
@@ -74,14 +75,15 @@ def load_data_groovy(v: str, e:str, lf:TextIOWrapper)->int:
     end_time = time.clock_gettime_ns(time.CLOCK_MONOTONIC)
     return end_time - start_time
 
-def log_metrics_sql(conn: psycopg.Connection, log_id:int, algo:str, dataset:str, type_:str, time:float)->None:
-    columns = ["id", "algo", "dataset", "type", "time"]
+def log_metrics_sql(conn: psycopg.Connection, log_id:int, algo:str, dataset:str, type_:str, time:float, vertex:int, edge:int, nodes:int)->None:
+    columns = ["id", "algo", "dataset", "type", "time", "vertex", "edge", "nodes"]
     cur = conn.cursor()
     query = sql.SQL("INSERT INTO gn_test ({}) VALUES ({})").format(
             sql.SQL(', ').join(map(sql.Identifier, columns)),
             sql.SQL(', ').join(sql.Placeholder() * len(columns)))
 
-    cur.execute(query, (log_id, algo, dataset, type_, time))
+    time_ms = time // 1000000
+    cur.execute(query, (log_id, algo, dataset, type_, time_ms, vertex, edge, nodes))
     conn.commit()
     cur.close()    
 
@@ -126,8 +128,10 @@ def main():
         configs = yaml.safe_load(yml_file)
 
     # sql params
-    id_ = int(configs["config"]["id"])
-    algo = configs["config"]["algo"]
+    ids = [int(x.strip()) for x in configs["config"]["ids"].split(",")]
+    algos = [x.strip() for x in configs["config"]["algos"].split(",")]
+    id_algos = list(zip(ids, algos))
+    nodes = configs["config"]["nodes"]
        
     log_file = configs["config"]["log_file"]
     lf = open(log_file, "w+")
@@ -141,6 +145,7 @@ def main():
     user_ps = configs["postgres"]["ps"]
     pg_user = configs["postgres"]["user"]
 
+    dataset = configs["dataset"]["name"]
     vertex_file = configs["dataset"]["vertex"]
     edge_file = configs["dataset"]["edges"] 
     
@@ -154,7 +159,7 @@ def main():
     try:
         sess = DriverRemoteConnection(f"ws://{janus_host}:{janus_port}/gremlin", 'g')
     except:
-        lf.write("Error: could not connect to graphscope cluster\n")
+        lf.write("Error: could not connect to janusgraph cluster\n")
         lf.close()
         conn.close()    
         quit(1)
@@ -165,16 +170,21 @@ def main():
     duration = load_data(sess, g, vertex_file, edge_file, lf)
     vertex_num = graph_vertex_count(g)
     edge_count = graph_edge_count(g)
-    #log_metrics_sql(conn, id_, algo, dataset, "loading", duration)
+    if bool(configs["load_data"]):
+        for entry in id_algos:
+            log_metrics_sql(conn, entry[0], entry[1], dataset, "loading", duration, vertex_num, edge_count, nodes)
 
     func_d = {'bfs': bfs, 'pr':pr, 'wcc':wcc, 'cdlp':cdlp, 'lcc':lcc, 'sssp':sssp}
 
-    start_time = time.clock_gettime_ns(time.CLOCK_MONOTONIC)
-    func_d[algo](g)
-    end_time = time.clock_gettime_ns(time.CLOCK_MONOTONIC)
+    for entry in id_algos:
+        requests.post("http://notifier:8080/starting", params={"id": entry[0]})
+        start_time = time.clock_gettime_ns(time.CLOCK_MONOTONIC)
+        func_d[entry[1]](g)
+        end_time = time.clock_gettime_ns(time.CLOCK_MONOTONIC)
+        requests.post("http://notifier:8080/stopping", params={"id": entry[0]})
 
-    duration = end_time - start_time
-    #log_metrics_sql(conn, id_, algo, dataset, "runtime", duration)
+        duration = end_time - start_time
+        log_metrics_sql(conn, entry[0], entry[1], dataset, "runtime", duration, vertex_num, edge_count, nodes)
 
     lf.close()
     sess.close()
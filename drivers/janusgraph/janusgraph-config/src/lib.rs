@@ -0,0 +1,230 @@
+use std::collections::{HashMap, HashSet};
+
+use anyhow::{bail, Result};
+use common::{
+    driver_config::{DriverConfig, NodePool},
+    traverse_yaml_mut,
+};
+use futures_util::{StreamExt, TryStreamExt};
+use k8s_openapi::api::{
+    apps::v1::StatefulSet,
+    core::v1::{Pod, Service},
+};
+use kube::{
+    api::ListParams,
+    runtime::{watcher, WatchStreamExt},
+    Api, Client,
+};
+use serde_yaml::Mapping;
+use tokio::fs;
+use tracing::info;
+
+const LABELS: &str = "app=janusgraph";
+
+/// Storage backend the janusgraph servers keep the graph in
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Backend {
+    Cassandra,
+    Scylla,
+    BerkeleyDb,
+}
+
+impl Backend {
+    fn from_options(options: &Mapping) -> Result<Self> {
+        Ok(match options.get("backend").and_then(|x| x.as_str()) {
+            None | Some("cassandra") => Self::Cassandra,
+            Some("scylla") | Some("scylladb") => Self::Scylla,
+            Some("berkeleydb") | Some("berkeleyje") => Self::BerkeleyDb,
+            Some(x) => bail!(
+                "unknown janusgraph storage backend {x}, expected cassandra, scylla or berkeleydb"
+            ),
+        })
+    }
+
+    /// Properties template of the janusgraph image
+    fn template(self) -> &'static str {
+        match self {
+            Self::Cassandra | Self::Scylla => "cql",
+            Self::BerkeleyDb => "berkeleyje",
+        }
+    }
+
+    /// Manifest of the storage nodes, berkeleydb is embedded in the server
+    fn manifest(self) -> Option<&'static str> {
+        match self {
+            Self::Cassandra => Some("cassandra.yaml"),
+            Self::Scylla => Some("scylla.yaml"),
+            Self::BerkeleyDb => None,
+        }
+    }
+}
+
+pub struct Janusgraph;
+
+impl Janusgraph {
+    /// Apply the resources & node pool to a statefulset manifest
+    fn patch_statefulset(
+        manifest: &mut serde_yaml::Value,
+        replicas: usize,
+        pool: Option<&NodePool>,
+        options: &Mapping,
+    ) -> Result<()> {
+        *traverse_yaml_mut(manifest, "spec.replicas")?.unwrap() = replicas.into();
+
+        if options.contains_key("cpu") {
+            *traverse_yaml_mut(
+                manifest,
+                "spec.template.spec.containers.0.resources.requests.cpu",
+            )?
+            .unwrap() = options.get("cpu").unwrap().clone();
+        }
+
+        if options.contains_key("memory") {
+            *traverse_yaml_mut(
+                manifest,
+                "spec.template.spec.containers.0.resources.requests.memory",
+            )?
+            .unwrap() = options.get("memory").unwrap().clone();
+        }
+
+        if options.contains_key("storage") {
+            *traverse_yaml_mut(
+                manifest,
+                "spec.volumeClaimTemplates.0.spec.resources.requests.storage",
+            )?
+            .unwrap() = options.get("storage").unwrap().clone();
+        }
+
+        if let Some(pool) = pool {
+            manifest["spec"]["template"]["spec"]["nodeSelector"] = pool.node_selector();
+        }
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl DriverConfig for Janusgraph {
+    fn name(&self) -> String {
+        "janusgraph".to_owned()
+    }
+
+    async fn get_platform_config(
+        &self,
+        _: usize,
+        namespace: &str,
+    ) -> Result<HashMap<String, String>> {
+        let client = Client::try_default().await?;
+        let services: Api<Service> = Api::namespaced(client, namespace);
+        let server = services.get("janusgraph").await?;
+        Ok(HashMap::from([
+            ("host".into(), "janusgraph".into()),
+            (
+                "port".into(),
+                server.spec.unwrap().ports.unwrap()[0].port.to_string(),
+            ),
+        ]))
+    }
+
+    async fn scale_service(
+        &self,
+        nodes: usize,
+        _: &str,
+        pool: Option<&NodePool>,
+        options: Option<serde_yaml::Value>,
+    ) -> Result<Vec<String>> {
+        let extra_vars = self.extra_vars(nodes, options.as_ref())?;
+        let options = match options {
+            Some(s) => s.as_mapping().unwrap().to_owned(),
+            None => Mapping::new(),
+        };
+        let backend = Backend::from_options(&options)?;
+        fs::create_dir_all(format!("drivers/{}/tmp", self.name())).await?;
+
+        if let Some(manifest) = backend.manifest() {
+            let f = fs::read_to_string(format!("drivers/{}/{manifest}", self.name())).await?;
+            let mut storage: serde_yaml::Value = serde_yaml::from_str(&f)?;
+            Self::patch_statefulset(&mut storage, nodes, pool, &options)?;
+            fs::write(
+                format!("drivers/{}/tmp/storage.yaml", self.name()),
+                serde_yaml::to_string(&storage)?,
+            )
+            .await?;
+        }
+
+        let f = fs::read_to_string(format!("drivers/{}/janusgraph.yaml", self.name())).await?;
+        let mut server: serde_yaml::Value = serde_yaml::from_str(&f)?;
+        // an embedded berkeleydb can't be shared, so it is served by a single server
+        let replicas = match backend {
+            Backend::BerkeleyDb => {
+                let env = server["spec"]["template"]["spec"]["containers"][0]["env"]
+                    .as_sequence_mut()
+                    .unwrap();
+                env.retain(|x| x["name"] == "JANUS_PROPS_TEMPLATE");
+                env[0]["value"] = backend.template().into();
+                1
+            }
+            _ => nodes,
+        };
+        Self::patch_statefulset(&mut server, replicas, pool, &options)?;
+        fs::write(
+            format!("drivers/{}/tmp/janusgraph.yaml", self.name()),
+            serde_yaml::to_string(&server)?,
+        )
+        .await?;
+
+        Ok(extra_vars)
+    }
+
+    fn extra_vars(&self, _: usize, options: Option<&serde_yaml::Value>) -> Result<Vec<String>> {
+        let options = match options {
+            Some(s) => s.as_mapping().unwrap().to_owned(),
+            None => Mapping::new(),
+        };
+        let backend = Backend::from_options(&options)?;
+        Ok(vec![format!("storage_backend={}", backend.template())])
+    }
+
+    async fn metrics_pod_ids(&self, namespace: &str) -> Result<Vec<String>> {
+        let client = Client::try_default().await?;
+        let pods: Api<Pod> = Api::namespaced(client, namespace);
+        let pods = pods.list(&ListParams::default().labels(LABELS)).await?;
+        Ok(pods
+            .items
+            .into_iter()
+            .map(|x| x.metadata.name.unwrap())
+            .collect())
+    }
+
+    async fn wait_for_service_ready(&self, _: usize, namespace: &str) -> Result<()> {
+        let client = Client::try_default().await?;
+        let ss: Api<StatefulSet> = Api::namespaced(client, namespace);
+        // the servers, and the storage nodes unless berkeleydb is used
+        let mut pending = ss
+            .list(&ListParams::default().labels(LABELS))
+            .await?
+            .items
+            .into_iter()
+            .map(|x| x.metadata.name.unwrap())
+            .collect::<HashSet<_>>();
+        let wc = watcher::Config::default().labels(LABELS);
+
+        let status_check = |ss: &StatefulSet| {
+            if let Some(status) = &ss.status {
+                return status.replicas > 0 && status.ready_replicas == Some(status.replicas);
+            }
+            false
+        };
+
+        let mut res = watcher(ss, wc).applied_objects().default_backoff().boxed();
+        while let Ok(Some(s)) = res.try_next().await {
+            info!("got status update {}", s.metadata.name.as_ref().unwrap());
+            if status_check(&s) {
+                pending.remove(s.metadata.name.as_ref().unwrap());
+                if pending.is_empty() {
+                    break;
+                }
+            }
+        }
+        Ok(())
+    }
+}
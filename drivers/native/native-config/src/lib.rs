@@ -0,0 +1,42 @@
+use std::collections::HashMap;
+
+use anyhow::Result;
+use common::driver_config::{DriverConfig, NodePool};
+
+/// Baseline that runs the algorithms in its benchmark pod, so there is no platform to install,
+/// scale or wait for
+pub struct Native;
+
+#[async_trait::async_trait]
+impl DriverConfig for Native {
+    fn name(&self) -> String {
+        "native".to_owned()
+    }
+
+    async fn get_platform_config(&self, _: usize, _: &str) -> Result<HashMap<String, String>> {
+        Ok(HashMap::new())
+    }
+
+    async fn scale_service(
+        &self,
+        _: usize,
+        _: &str,
+        _: Option<&NodePool>,
+        _: Option<serde_yaml::Value>,
+    ) -> Result<Vec<String>> {
+        Ok(vec![])
+    }
+
+    // the benchmark pod doesn't exist yet when the pods to record are looked up
+    async fn metrics_pod_ids(&self, _: &str) -> Result<Vec<String>> {
+        Ok(vec![])
+    }
+
+    async fn wait_for_service_ready(&self, _: usize, _: &str) -> Result<()> {
+        Ok(())
+    }
+
+    fn writes_output(&self) -> bool {
+        true
+    }
+}
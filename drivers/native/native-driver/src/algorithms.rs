@@ -0,0 +1,348 @@
+use std::{
+    cmp::{Ordering, Reverse},
+    collections::{BinaryHeap, HashMap, VecDeque},
+    io::{BufWriter, Write},
+    path::Path,
+};
+
+use anyhow::{anyhow, bail, Result};
+
+use crate::graph::Graph;
+
+const PR_ITERATIONS: usize = 10;
+const PR_DAMPING: f64 = 0.85;
+const CDLP_ITERATIONS: usize = 10;
+
+/// Result of an algorithm for every vertex, in the order of the vertices of the graph
+pub enum Output {
+    Integers(Vec<i64>),
+    Floats(Vec<f64>),
+}
+
+impl Output {
+    pub fn len(&self) -> usize {
+        match self {
+            Output::Integers(x) => x.len(),
+            Output::Floats(x) => x.len(),
+        }
+    }
+
+    /// Write a `<vertex> <value>` line per vertex, as in the Graphalytics reference outputs
+    pub fn write(&self, graph: &Graph, path: &Path) -> Result<()> {
+        let mut file = BufWriter::new(std::fs::File::create(path)?);
+        for (v, id) in graph.ids.iter().enumerate() {
+            match self {
+                Output::Integers(x) => writeln!(file, "{id} {}", x[v])?,
+                Output::Floats(x) if x[v].is_infinite() => writeln!(file, "{id} infinity")?,
+                Output::Floats(x) => writeln!(file, "{id} {}", x[v])?,
+            }
+        }
+        file.flush()?;
+        Ok(())
+    }
+}
+
+/// Run an algorithm as defined by LDBC Graphalytics, `source` is the dataset id of the start
+/// vertex of bfs & sssp
+pub fn run(algo: &str, graph: &Graph, source: u64) -> Result<Output> {
+    let source = || {
+        graph
+            .vertex(source)
+            .ok_or_else(|| anyhow!("start vertex {source} is not in the graph"))
+    };
+    Ok(match algo {
+        "bfs" => Output::Integers(bfs(graph, source()?)),
+        "pr" => Output::Floats(pr(graph)),
+        "wcc" => Output::Integers(wcc(graph)),
+        "cdlp" => Output::Integers(cdlp(graph)),
+        "lcc" => Output::Floats(lcc(graph)),
+        "sssp" => Output::Floats(sssp(graph, source()?)),
+        _ => bail!("unknown algorithm {algo}"),
+    })
+}
+
+/// Depth of every vertex from the source, `i64::MAX` if it can't be reached
+fn bfs(graph: &Graph, source: usize) -> Vec<i64> {
+    let mut depth = vec![i64::MAX; graph.ids.len()];
+    let mut queue = VecDeque::from([source]);
+    depth[source] = 0;
+    while let Some(v) = queue.pop_front() {
+        for u in graph.out_neighbours(v) {
+            if depth[u] == i64::MAX {
+                depth[u] = depth[v] + 1;
+                queue.push_back(u);
+            }
+        }
+    }
+    depth
+}
+
+/// PageRank, the rank of vertices without outgoing edges is spread over all vertices
+fn pr(graph: &Graph) -> Vec<f64> {
+    let n = graph.ids.len() as f64;
+    let mut rank = vec![1.0 / n; graph.ids.len()];
+    for _ in 0..PR_ITERATIONS {
+        let dangling = (0..graph.ids.len())
+            .filter(|x| graph.out[*x].is_empty())
+            .map(|x| rank[x])
+            .sum::<f64>();
+        let contribution = (0..graph.ids.len())
+            .map(|x| match graph.out[x].len() {
+                0 => 0.0,
+                degree => rank[x] / degree as f64,
+            })
+            .collect::<Vec<_>>();
+        rank = (0..graph.ids.len())
+            .map(|v| {
+                let incoming = graph.in_neighbours(v).map(|u| contribution[u]).sum::<f64>();
+                (1.0 - PR_DAMPING) / n + PR_DAMPING * (incoming + dangling / n)
+            })
+            .collect();
+    }
+    rank
+}
+
+/// Weakly connected components, labelled with the smallest vertex id in them
+fn wcc(graph: &Graph) -> Vec<i64> {
+    fn root(parent: &mut [usize], mut v: usize) -> usize {
+        while parent[v] != v {
+            parent[v] = parent[parent[v]];
+            v = parent[v];
+        }
+        v
+    }
+
+    let mut parent = (0..graph.ids.len()).collect::<Vec<_>>();
+    for v in 0..graph.ids.len() {
+        for u in graph.out_neighbours(v) {
+            let (a, b) = (root(&mut parent, v), root(&mut parent, u));
+            if a != b {
+                parent[a.max(b)] = a.min(b);
+            }
+        }
+    }
+
+    let mut label = HashMap::new();
+    for v in 0..graph.ids.len() {
+        let r = root(&mut parent, v);
+        let id = graph.ids[v] as i64;
+        label
+            .entry(r)
+            .and_modify(|x: &mut i64| *x = id.min(*x))
+            .or_insert(id);
+    }
+    (0..graph.ids.len())
+        .map(|v| label[&root(&mut parent, v)])
+        .collect()
+}
+
+/// Community detection with synchronous label propagation, ties go to the smallest label
+fn cdlp(graph: &Graph) -> Vec<i64> {
+    let mut labels = graph.ids.iter().map(|x| *x as i64).collect::<Vec<_>>();
+    for _ in 0..CDLP_ITERATIONS {
+        labels = (0..graph.ids.len())
+            .map(|v| {
+                let mut counts = HashMap::new();
+                for u in graph.neighbours(v) {
+                    *counts.entry(labels[u]).or_insert(0) += 1;
+                }
+                counts
+                    .into_iter()
+                    .max_by(|a, b| a.1.cmp(&b.1).then(b.0.cmp(&a.0)))
+                    .map(|x| x.0)
+                    .unwrap_or(labels[v])
+            })
+            .collect();
+    }
+    labels
+}
+
+/// Local clustering coefficient, the share of the pairs of neighbours that are linked
+fn lcc(graph: &Graph) -> Vec<f64> {
+    // marks the neighbours of the current vertex, by the index of that vertex + 1
+    let mut mark = vec![0; graph.ids.len()];
+    (0..graph.ids.len())
+        .map(|v| {
+            let mut neighbours = Vec::new();
+            for u in graph.neighbours(v) {
+                if u != v && mark[u] != v + 1 {
+                    mark[u] = v + 1;
+                    neighbours.push(u);
+                }
+            }
+            if neighbours.len() < 2 {
+                return 0.0;
+            }
+            let links = neighbours
+                .iter()
+                .map(|u| {
+                    graph
+                        .out_neighbours(*u)
+                        .filter(|w| w != u && mark[*w] == v + 1)
+                        .count()
+                })
+                .sum::<usize>();
+            let pairs = neighbours.len() * (neighbours.len() - 1);
+            links as f64 / pairs as f64
+        })
+        .collect()
+}
+
+/// Distance of every vertex from the source over the edge weights, infinite if it can't be
+/// reached
+fn sssp(graph: &Graph, source: usize) -> Vec<f64> {
+    #[derive(PartialEq)]
+    struct Distance(f64);
+    impl Eq for Distance {}
+    impl PartialOrd for Distance {
+        fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+            Some(self.cmp(other))
+        }
+    }
+    impl Ord for Distance {
+        fn cmp(&self, other: &Self) -> Ordering {
+            self.0.total_cmp(&other.0)
+        }
+    }
+
+    let mut distance = vec![f64::INFINITY; graph.ids.len()];
+    let mut heap = BinaryHeap::from([(Reverse(Distance(0.0)), source)]);
+    distance[source] = 0.0;
+    while let Some((Reverse(Distance(d)), v)) = heap.pop() {
+        if d > distance[v] {
+            continue;
+        }
+        for (u, weight) in &graph.out[v] {
+            let next = d + weight;
+            if next < distance[*u] {
+                distance[*u] = next;
+                heap.push((Reverse(Distance(next)), *u));
+            }
+        }
+    }
+    distance
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs};
+
+    use super::*;
+
+    /// Load a graph from a vertex & an edge file written to the temporary directory
+    fn graph(name: &str, vertices: &str, edges: &str, directed: bool) -> Graph {
+        let dir = env::temp_dir().join(format!("native-driver-{}-{name}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let (v, e) = (dir.join("graph.v"), dir.join("graph.e"));
+        fs::write(&v, vertices).unwrap();
+        fs::write(&e, edges).unwrap();
+        let graph = Graph::load(v.to_str().unwrap(), e.to_str().unwrap(), directed).unwrap();
+        fs::remove_dir_all(dir).unwrap();
+        graph
+    }
+
+    /// A weighted triangle 1 2 3 with a tail 3 - 4, and the isolated vertex 5
+    fn triangle(name: &str) -> Graph {
+        graph(
+            name,
+            "1\n2\n3\n4\n5\n",
+            "1 2 1\n2 3 2\n1 3 5\n3 4 1\n",
+            false,
+        )
+    }
+
+    fn integers(output: Output) -> Vec<i64> {
+        match output {
+            Output::Integers(x) => x,
+            Output::Floats(_) => panic!("expected integers"),
+        }
+    }
+
+    fn floats(output: Output) -> Vec<f64> {
+        match output {
+            Output::Floats(x) => x,
+            Output::Integers(_) => panic!("expected floats"),
+        }
+    }
+
+    #[test]
+    fn bfs_depths() {
+        let g = triangle("bfs");
+        let depth = integers(run("bfs", &g, 1).unwrap());
+        assert_eq!(depth, [0, 1, 1, 2, i64::MAX]);
+
+        // 1 -> 2 -> 3 -> 1, only followed forwards
+        let g = graph("bfs-directed", "1\n2\n3\n", "1 2\n2 3\n3 1\n", true);
+        assert_eq!(integers(run("bfs", &g, 2).unwrap()), [2, 0, 1]);
+    }
+
+    #[test]
+    fn sssp_distances() {
+        let g = triangle("sssp");
+        let distance = floats(run("sssp", &g, 1).unwrap());
+        // 3 is closer through 2 than over the edge of weight 5
+        assert_eq!(distance, [0.0, 1.0, 3.0, 4.0, f64::INFINITY]);
+    }
+
+    #[test]
+    fn unknown_start_vertex() {
+        let g = triangle("source");
+        assert!(run("bfs", &g, 9).is_err());
+        assert!(run("bogus", &g, 1).is_err());
+    }
+
+    #[test]
+    fn wcc_labels() {
+        let g = triangle("wcc");
+        assert_eq!(integers(run("wcc", &g, 1).unwrap()), [1, 1, 1, 1, 5]);
+
+        // the components of a directed graph ignore the direction
+        let g = graph("wcc-directed", "4\n2\n3\n", "3 4\n", true);
+        assert_eq!(integers(run("wcc", &g, 4).unwrap()), [3, 2, 3]);
+    }
+
+    #[test]
+    fn cdlp_communities() {
+        // two triangles, each converging on the smallest label after 2 iterations
+        let g = graph(
+            "cdlp",
+            "1\n2\n3\n4\n5\n6\n",
+            "1 2\n2 3\n1 3\n4 5\n5 6\n4 6\n",
+            false,
+        );
+        assert_eq!(integers(run("cdlp", &g, 1).unwrap()), [1, 1, 1, 4, 4, 4]);
+    }
+
+    #[test]
+    fn lcc_coefficients() {
+        let g = triangle("lcc");
+        let lcc = floats(run("lcc", &g, 1).unwrap());
+        // 3 has 3 neighbours, of which only 1 & 2 are linked
+        assert_eq!(lcc, [1.0, 1.0, 1.0 / 3.0, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn pr_ranks() {
+        let g = triangle("pr");
+        let rank = floats(run("pr", &g, 1).unwrap());
+        // the rank of the isolated vertex is spread over all vertices, so none is lost
+        assert!((rank.iter().sum::<f64>() - 1.0).abs() < 1e-9);
+        assert!((rank[0] - rank[1]).abs() < 1e-12);
+        assert!(rank[2] > rank[0] && rank[0] > rank[3] && rank[3] > rank[4]);
+
+        // a single edge splits the rank evenly
+        let g = graph("pr-pair", "1\n2\n", "1 2\n", false);
+        assert_eq!(floats(run("pr", &g, 1).unwrap()), [0.5, 0.5]);
+    }
+
+    #[test]
+    fn write_output() {
+        let g = triangle("write");
+        let output = run("sssp", &g, 1).unwrap();
+        let file = env::temp_dir().join(format!("native-driver-{}-output", std::process::id()));
+        output.write(&g, &file).unwrap();
+        let written = fs::read_to_string(&file).unwrap();
+        fs::remove_file(file).unwrap();
+        assert_eq!(written, "1 0\n2 1\n3 3\n4 4\n5 infinity\n");
+    }
+}
@@ -0,0 +1,96 @@
+use std::{collections::HashMap, fs};
+
+use anyhow::{anyhow, Context, Result};
+
+/// Graph of a dataset, with the vertices numbered in the order of the vertex file
+pub struct Graph {
+    /// Dataset ids of the vertices
+    pub ids: Vec<u64>,
+    /// Outgoing edges with their weight, both directions of an edge in an undirected graph
+    pub out: Vec<Vec<(usize, f64)>>,
+    /// Incoming edges, only kept for directed graphs
+    incoming: Vec<Vec<usize>>,
+    pub edges: usize,
+    pub directed: bool,
+}
+
+impl Graph {
+    /// Read the `.v` & `.e` files of a dataset, the edge file has a `src dst [weight]` line per edge
+    pub fn load(vertex_file: &str, edge_file: &str, directed: bool) -> Result<Self> {
+        let vertices = fs::read_to_string(vertex_file)
+            .with_context(|| format!("could not read {vertex_file}"))?;
+        let ids = vertices
+            .lines()
+            .filter_map(|x| x.split_ascii_whitespace().next())
+            .map(|x| x.parse::<u64>())
+            .collect::<Result<Vec<_>, _>>()
+            .with_context(|| format!("invalid vertex id in {vertex_file}"))?;
+        let index = ids
+            .iter()
+            .enumerate()
+            .map(|(i, x)| (*x, i))
+            .collect::<HashMap<_, _>>();
+
+        let mut graph = Graph {
+            out: vec![Vec::new(); ids.len()],
+            incoming: vec![Vec::new(); if directed { ids.len() } else { 0 }],
+            ids,
+            edges: 0,
+            directed,
+        };
+        let edges =
+            fs::read_to_string(edge_file).with_context(|| format!("could not read {edge_file}"))?;
+        for (n, line) in edges.lines().enumerate() {
+            let mut fields = line.split_ascii_whitespace();
+            let (Some(src), Some(dst)) = (fields.next(), fields.next()) else {
+                continue;
+            };
+            let vertex = |x: &str| {
+                x.parse::<u64>()
+                    .ok()
+                    .and_then(|x| index.get(&x).copied())
+                    .ok_or_else(|| anyhow!("unknown vertex {x} on line {} of {edge_file}", n + 1))
+            };
+            let (src, dst) = (vertex(src)?, vertex(dst)?);
+            let weight = match fields.next() {
+                Some(x) => x
+                    .parse::<f64>()
+                    .with_context(|| format!("invalid weight on line {} of {edge_file}", n + 1))?,
+                None => 1.0,
+            };
+
+            graph.out[src].push((dst, weight));
+            if directed {
+                graph.incoming[dst].push(src);
+            } else {
+                graph.out[dst].push((src, weight));
+            }
+            graph.edges += 1;
+        }
+        Ok(graph)
+    }
+
+    /// Index of the vertex with a dataset id
+    pub fn vertex(&self, id: u64) -> Option<usize> {
+        self.ids.iter().position(|x| *x == id)
+    }
+
+    pub fn out_neighbours(&self, v: usize) -> impl Iterator<Item = usize> + '_ {
+        self.out[v].iter().map(|x| x.0)
+    }
+
+    pub fn in_neighbours(&self, v: usize) -> Box<dyn Iterator<Item = usize> + '_> {
+        match self.directed {
+            true => Box::new(self.incoming[v].iter().copied()),
+            false => Box::new(self.out_neighbours(v)),
+        }
+    }
+
+    /// Vertices connected to `v` in any direction, a vertex linked both ways appears twice
+    pub fn neighbours(&self, v: usize) -> Box<dyn Iterator<Item = usize> + '_> {
+        match self.directed {
+            true => Box::new(self.out_neighbours(v).chain(self.in_neighbours(v))),
+            false => Box::new(self.out_neighbours(v)),
+        }
+    }
+}
@@ -0,0 +1,201 @@
+use std::{
+    fs::{self, File},
+    path::Path,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use anyhow::{bail, Context, Result};
+use hyper::{Body, Client, Method, Request};
+use serde::Deserialize;
+use tokio_postgres::NoTls;
+use tracing::info;
+use tracing_subscriber::fmt::writer::MakeWriterExt;
+
+use crate::graph::Graph;
+
+mod algorithms;
+mod graph;
+
+const NOTIFIER: &str = "http://notifier:8080";
+
+#[derive(Deserialize)]
+struct DriverConfig {
+    config: RunConfig,
+    postgres: PostgresConfig,
+    dataset: DatasetConfig,
+    load_data: bool,
+}
+
+#[derive(Deserialize)]
+struct RunConfig {
+    ids: String,
+    algos: String,
+    log_file: String,
+    nodes: i32,
+    output_dir: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct PostgresConfig {
+    host: String,
+    port: u16,
+    db: String,
+    user: String,
+    ps: String,
+}
+
+#[derive(Deserialize)]
+struct DatasetConfig {
+    name: String,
+    vertex: String,
+    edges: String,
+    directed: bool,
+    start_vertex: u64,
+}
+
+/// Tell the runner a run starts or stops, so it records the metrics of the run
+async fn notify(event: &str, id: i32) -> Result<()> {
+    let request = Request::builder()
+        .method(Method::POST)
+        .uri(format!("{NOTIFIER}/{event}?id={id}"))
+        .body(Body::empty())?;
+    let response = Client::new().request(request).await?;
+    if !response.status().is_success() {
+        bail!("notifier answered {} to {event}", response.status());
+    }
+    Ok(())
+}
+
+/// Row of the `gn_test` table the python drivers write their times to
+struct Measurement<'a> {
+    id: i32,
+    algo: &'a str,
+    kind: &'a str,
+    time: Duration,
+}
+
+async fn log_metrics_sql(
+    client: &tokio_postgres::Client,
+    config: &DriverConfig,
+    graph: &Graph,
+    m: Measurement<'_>,
+) -> Result<()> {
+    client
+        .execute(
+            "INSERT INTO gn_test(id, algo, dataset, type, time, vertex, edge, nodes) \
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8)",
+            &[
+                &m.id,
+                &m.algo,
+                &config.dataset.name,
+                &m.kind,
+                &(m.time.as_millis() as i32),
+                &(graph.ids.len() as i32),
+                &(graph.edges as i32),
+                &config.config.nodes,
+            ],
+        )
+        .await?;
+    Ok(())
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let Some(path) = std::env::args().nth(1) else {
+        bail!("usage: native-driver <config.yaml>");
+    };
+    let config: DriverConfig = serde_yaml::from_str(
+        &std::fs::read_to_string(&path).with_context(|| format!("could not read {path}"))?,
+    )?;
+
+    let log = File::create(&config.config.log_file)?;
+    tracing_subscriber::fmt()
+        .with_ansi(false)
+        .with_writer(std::io::stdout.and(Mutex::new(log)))
+        .init();
+
+    let ids = config
+        .config
+        .ids
+        .split(',')
+        .map(|x| x.trim().parse::<i32>())
+        .collect::<Result<Vec<_>, _>>()?;
+    let algos = config
+        .config
+        .algos
+        .split(',')
+        .map(|x| x.trim())
+        .collect::<Vec<_>>();
+
+    let pg = &config.postgres;
+    let (client, connection) = tokio_postgres::connect(
+        &format!(
+            "host={} port={} dbname={} user={} password={}",
+            pg.host, pg.port, pg.db, pg.user, pg.ps
+        ),
+        NoTls,
+    )
+    .await
+    .context("could not connect to postgresql database")?;
+    tokio::spawn(connection);
+    client
+        .batch_execute(
+            "CREATE TABLE IF NOT EXISTS gn_test(id INTEGER, algo VARCHAR(256), \
+             dataset VARCHAR(256), type VARCHAR(256), time INTEGER, vertex INTEGER, \
+             edge INTEGER, nodes INTEGER, seq BIGSERIAL)",
+        )
+        .await?;
+
+    let start = Instant::now();
+    let graph = Graph::load(
+        &config.dataset.vertex,
+        &config.dataset.edges,
+        config.dataset.directed,
+    )?;
+    let loading = start.elapsed();
+    info!(
+        "loaded {} with {} vertices & {} edges in {loading:?}",
+        config.dataset.name,
+        graph.ids.len(),
+        graph.edges
+    );
+    // the graph is loaded by every job, but the loading time is only recorded with the first
+    if config.load_data {
+        for (id, algo) in ids.iter().zip(&algos) {
+            let m = Measurement {
+                id: *id,
+                algo,
+                kind: "loading",
+                time: loading,
+            };
+            log_metrics_sql(&client, &config, &graph, m).await?;
+        }
+    }
+
+    for (id, algo) in ids.iter().zip(&algos) {
+        info!("starting {algo} with id {id}");
+        notify("starting", *id).await?;
+        let start = Instant::now();
+        let output = algorithms::run(algo, &graph, config.dataset.start_vertex)?;
+        let runtime = start.elapsed();
+        notify("stopping", *id).await?;
+        info!(
+            "computed {algo} for {} vertices in {runtime:?}",
+            output.len()
+        );
+        if let Some(dir) = &config.config.output_dir {
+            fs::create_dir_all(dir)?;
+            output.write(&graph, &Path::new(dir).join(id.to_string()))?;
+        }
+
+        let m = Measurement {
+            id: *id,
+            algo,
+            kind: "runtime",
+            time: runtime,
+        };
+        log_metrics_sql(&client, &config, &graph, m).await?;
+    }
+    Ok(())
+}
@@ -1,7 +1,10 @@
 use std::collections::HashMap;
 
 use anyhow::Result;
-use common::{driver_config::DriverConfig, traverse_yaml_mut};
+use common::{
+    driver_config::{DriverConfig, NodePool},
+    traverse_yaml_mut,
+};
 use futures_util::{StreamExt, TryStreamExt};
 use k8s_openapi::api::{
     apps::v1::StatefulSet,
@@ -24,7 +27,11 @@ impl DriverConfig for Neo4j {
         "neo4j".to_owned()
     }
 
-    async fn get_platform_config(&self, mut nodes: usize) -> Result<HashMap<String, String>> {
+    async fn get_platform_config(
+        &self,
+        mut nodes: usize,
+        _: &str,
+    ) -> Result<HashMap<String, String>> {
         if nodes == 2 {
             nodes = 3;
         }
@@ -41,8 +48,11 @@ impl DriverConfig for Neo4j {
     async fn scale_service(
         &self,
         mut nodes: usize,
+        namespace: &str,
+        pool: Option<&NodePool>,
         options: Option<serde_yaml::Value>,
     ) -> Result<Vec<String>> {
+        let extra_vars = self.extra_vars(nodes, options.as_ref())?;
         let f = fs::read_to_string(&format!("drivers/{}/values.yaml", self.name())).await?;
         let mut values: serde_yaml::Value = serde_yaml::from_str(&f)?;
 
@@ -51,7 +61,7 @@ impl DriverConfig for Neo4j {
         }
 
         let client = Client::try_default().await?;
-        let service: Api<Service> = Api::default_namespaced(client);
+        let service: Api<Service> = Api::namespaced(client, namespace);
         let nfs_ip = service
             .get("nfs-service")
             .await?
@@ -83,6 +93,9 @@ impl DriverConfig for Neo4j {
         *traverse_yaml_mut(&mut values, "volumes.import.volumeClaimTemplate.nfs.server")?
             .unwrap() = nfs_ip.clone().into();
         *traverse_yaml_mut(&mut values, "neo4j.minimumClusterSize")?.unwrap() = nodes.into();
+        if let Some(pool) = pool {
+            values["nodeSelector"] = pool.node_selector();
+        }
 
         let mut values_other = values.clone();
         traverse_yaml_mut(&mut values_other, "volumes")?
@@ -121,12 +134,19 @@ impl DriverConfig for Neo4j {
         )
         .await?;
 
+        Ok(extra_vars)
+    }
+
+    fn extra_vars(&self, mut nodes: usize, _: Option<&serde_yaml::Value>) -> Result<Vec<String>> {
+        if nodes == 2 {
+            nodes = 3;
+        }
         Ok(vec![format!("num_nodes={nodes}")])
     }
 
-    async fn metrics_pod_ids(&self) -> Result<Vec<String>> {
+    async fn metrics_pod_ids(&self, namespace: &str) -> Result<Vec<String>> {
         let client = Client::try_default().await?;
-        let pods: Api<Pod> = Api::default_namespaced(client);
+        let pods: Api<Pod> = Api::namespaced(client, namespace);
         let pods = pods
             .list(&ListParams::default().labels("helm.neo4j.com/pod_category=neo4j-instance"))
             .await?;
@@ -137,13 +157,13 @@ impl DriverConfig for Neo4j {
             .collect())
     }
 
-    async fn wait_for_service_ready(&self, mut nodes: usize) -> Result<()> {
+    async fn wait_for_service_ready(&self, mut nodes: usize, namespace: &str) -> Result<()> {
         if nodes == 2 {
             nodes = 3;
         }
 
         let client = Client::try_default().await?;
-        let ss: Api<StatefulSet> = Api::default_namespaced(client);
+        let ss: Api<StatefulSet> = Api::namespaced(client, namespace);
         let wc = watcher::Config::default().labels("helm.neo4j.com/neo4j.name=neo");
 
         let status_check = |ss: StatefulSet| {
@@ -21,7 +21,7 @@ def check_table(conn: psycopg.Connection) -> None:
 
     if not ret.fetchone()[0]:
         query = sql.SQL(
-            "CREATE TABLE gn_test(id INTEGER, algo VARCHAR(256), dataset VARCHAR(256), type VARCHAR(256), time INTEGER, vertex INTEGER, edge INTEGER, nodes INTEGER)"
+            "CREATE TABLE gn_test(id INTEGER, algo VARCHAR(256), dataset VARCHAR(256), type VARCHAR(256), time INTEGER, vertex INTEGER, edge INTEGER, nodes INTEGER, seq BIGSERIAL)"
         )
         cur.execute(query)
 
@@ -379,9 +379,9 @@ def main():
         id_ = entry[0]
         algo = entry[1]
 
-        requests.post("http://notifier:8080/starting")
+        requests.post("http://notifier:8080/starting", params={"id": id_})
         dur = func_d[algo](config, gds, G)
-        requests.post("http://notifier:8080/stopping")
+        requests.post("http://notifier:8080/stopping", params={"id": id_})
 
         if dur > 0:
             log_metrics_sql(
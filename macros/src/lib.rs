@@ -8,6 +8,18 @@ struct BuildConfig {
     providers: Vec<String>,
 }
 
+/// Name of the type a crate exports, `static-hosts` exports `StaticHosts`
+fn camel_case(name: &str) -> String {
+    name.split('-')
+        .flat_map(|x| {
+            x.chars()
+                .take(1)
+                .flat_map(|f| f.to_uppercase())
+                .chain(x.chars().skip(1))
+        })
+        .collect()
+}
+
 #[proc_macro]
 pub fn include_drivers(_: TokenStream) -> TokenStream {
     let config: BuildConfig =
@@ -16,13 +28,7 @@ pub fn include_drivers(_: TokenStream) -> TokenStream {
     let mut drivers_caps = Vec::new();
 
     for p in config.drivers {
-        let d_cap: String = p
-            .chars()
-            .take(1)
-            .flat_map(|f| f.to_uppercase())
-            .chain(p.chars().skip(1))
-            .collect();
-        let d_name_caps = format_ident!("{}", d_cap);
+        let d_name_caps = format_ident!("{}", camel_case(&p));
         let d_name = format_ident!("{}_config", p);
 
         drivers.push(d_name);
@@ -43,14 +49,8 @@ pub fn include_providers(_: TokenStream) -> TokenStream {
     let mut provider_caps = Vec::new();
 
     for p in config.providers {
-        let d_cap: String = p
-            .chars()
-            .take(1)
-            .flat_map(|f| f.to_uppercase())
-            .chain(p.chars().skip(1))
-            .collect();
-        let p_name_caps = format_ident!("{}", d_cap);
-        let p_name = format_ident!("{}", p);
+        let p_name_caps = format_ident!("{}", camel_case(&p));
+        let p_name = format_ident!("{}", p.replace('-', "_"));
 
         provider.push(p_name);
         provider_caps.push(p_name_caps);
@@ -14,7 +14,8 @@ type GrpcConfig struct {
 }
 
 type K8sConfig struct {
-	InClusterConfig bool `yaml:"in_cluster_config"`
+	InClusterConfig bool   `yaml:"in_cluster_config"`
+	Namespace       string `yaml:"namespace"`
 }
 
 type GlobalConfig struct {
@@ -17,11 +17,14 @@ func FromFile(path string) (GlobalConfig, error) {
 	if err != nil {
 		log.Fatalf("Unmarshal: %v\n", err)
 	}
+	if cfg.K8s.Namespace == "" {
+		cfg.K8s.Namespace = "default"
+	}
 
 	return *cfg, nil
 }
 
-func FromCmd(sqlHost, sqlUsername, sqlPassword, sqlDb, grpcHost string, sqlPort, grpcPort int64, k8sAuth bool) GlobalConfig {
+func FromCmd(sqlHost, sqlUsername, sqlPassword, sqlDb, grpcHost string, sqlPort, grpcPort int64, k8sAuth bool, k8sNamespace string) GlobalConfig {
 	cfg := GlobalConfig{
 		Sql: SqlConfig{
 			Host:     sqlHost,
@@ -34,7 +37,7 @@ func FromCmd(sqlHost, sqlUsername, sqlPassword, sqlDb, grpcHost string, sqlPort,
 			Host: grpcHost,
 			Port: grpcPort,
 		},
-		K8s: K8sConfig{InClusterConfig: k8sAuth},
+		K8s: K8sConfig{InClusterConfig: k8sAuth, Namespace: k8sNamespace},
 	}
 	return cfg
 }
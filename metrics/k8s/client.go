@@ -13,9 +13,10 @@ type Client struct {
 	restConfig    *rest.Config
 	clientset     *kubernetes.Clientset
 	metricsClient *metrics.Clientset
+	namespace     string
 }
 
-func NewClients() (Client, error) {
+func NewClients(namespace string) (Client, error) {
 
 	// Creates the in-cluster config
 	config, err := rest.InClusterConfig()
@@ -39,13 +40,14 @@ func NewClients() (Client, error) {
 		restConfig:    config,
 		clientset:     clientset,
 		metricsClient: mc,
+		namespace:     namespace,
 	}, nil
 
 }
 
 func (c *Client) GetMetrics(name string) (*v1beta1.PodMetrics, error) {
 	//metrics, err := c.metricsClient.MetricsV1beta1().PodMetricses(metav1.NamespaceAll).Get(context.TODO(), name, metav1.GetOptions{})
-	metrics, err := c.metricsClient.MetricsV1beta1().PodMetricses("default").Get(context.TODO(), name, metav1.GetOptions{})
+	metrics, err := c.metricsClient.MetricsV1beta1().PodMetricses(c.namespace).Get(context.TODO(), name, metav1.GetOptions{})
 	if err != nil {
 		return nil, err
 	}
@@ -22,7 +22,7 @@ func New(sqlCfg config.SqlConfig, k8sCfg config.K8sConfig, runId int64, interval
 	if err != nil {
 		log.Panicf("Unable to initalize connection to database: %v", err)
 	}
-	k8sClient, err := NewClients()
+	k8sClient, err := NewClients(k8sCfg.Namespace)
 	if err != nil {
 		log.Panicf("Unable to initalize K8s clients: %v", err)
 	}
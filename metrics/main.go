@@ -21,12 +21,13 @@ func main() {
 	grpcPort := flag.Int64("grpc-port", 9090, "the port of grpc server")
 
 	k8sAuthMethod := flag.Bool("k8s-auth", true, "the auth method of k8s API (only in-cluster implemented)")
+	k8sNamespace := flag.String("k8s-namespace", "default", "the namespace of the pods to record metrics of")
 
 	flag.Parse()
 
 	cfg := config.GlobalConfig{}
 	if *cfgPath == "" {
-		cfg = config.FromCmd(*psqlHost, *psqlUsername, *psqlPassword, *psqlDatabase, *grpcHost, *psqlPort, *grpcPort, *k8sAuthMethod)
+		cfg = config.FromCmd(*psqlHost, *psqlUsername, *psqlPassword, *psqlDatabase, *grpcHost, *psqlPort, *grpcPort, *k8sAuthMethod, *k8sNamespace)
 	} else {
 		var err error
 		cfg, err = config.FromFile(*cfgPath)
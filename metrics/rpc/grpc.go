@@ -5,34 +5,44 @@ import (
 	"graph-benchmarks/metrics-server/config"
 	"graph-benchmarks/metrics-server/k8s"
 	"log"
+	"sync"
 )
 
 type MetricsServer struct {
 	UnimplementedMetricsCollectorServer
 	k8sConfig config.K8sConfig
 	sqlConfig config.SqlConfig
-	worker    k8s.MetricsPollingWorker
+
+	// workers of the runs being recorded, several when drivers are benchmarked in parallel
+	workers map[int64]*k8s.MetricsPollingWorker
+	lock    sync.Mutex
 }
 
-func New(k8sCfg config.K8sConfig, sqlConfig config.SqlConfig) MetricsServer {
-	return MetricsServer{
+func New(k8sCfg config.K8sConfig, sqlConfig config.SqlConfig) *MetricsServer {
+	return &MetricsServer{
 		UnimplementedMetricsCollectorServer: UnimplementedMetricsCollectorServer{},
 		k8sConfig:                           k8sCfg,
 		sqlConfig:                           sqlConfig,
+		workers:                             map[int64]*k8s.MetricsPollingWorker{},
 	}
 }
 
 func (s *MetricsServer) StartRecording(ctx context.Context, req *Start) (*Ack, error) {
 	log.Printf("Received start recording request: %s", req)
-	var err error
-	s.worker, err = k8s.New(s.sqlConfig, s.k8sConfig, req.RunId, int64(req.Interval), req.PodIds)
+	worker, err := k8s.New(s.sqlConfig, s.k8sConfig, req.RunId, int64(req.Interval), req.PodIds)
 	if err != nil {
 		return &Ack{
 			Status:  false,
 			Message: "Unable to start metrics worker, check metrics server log.",
 		}, err
 	}
-	s.worker.Start()
+	s.lock.Lock()
+	if old, ok := s.workers[req.RunId]; ok {
+		old.Stop()
+	}
+	s.workers[req.RunId] = &worker
+	s.lock.Unlock()
+	worker.Start()
 
 	return &Ack{
 		Status:  true,
@@ -42,7 +52,17 @@ func (s *MetricsServer) StartRecording(ctx context.Context, req *Start) (*Ack, e
 
 func (s *MetricsServer) StopRecording(ctx context.Context, req *Stop) (*Ack, error) {
 	log.Printf("Received stop recording request: %s", req)
-	s.worker.Stop()
+	s.lock.Lock()
+	worker, ok := s.workers[req.RunId]
+	delete(s.workers, req.RunId)
+	s.lock.Unlock()
+	if !ok {
+		return &Ack{
+			Status:  false,
+			Message: "No metrics are recorded for this run.",
+		}, nil
+	}
+	worker.Stop()
 
 	return &Ack{
 		Status:  true,
@@ -24,10 +24,8 @@ func (s *Rpc) StartServer(grpcCfg config.GrpcConfig, k8sCfg config.K8sConfig, sq
 	var opts []grpc.ServerOption
 	s.handler = grpc.NewServer(opts...)
 	//reflection.Register(s.handler)
-	metricsServer := MetricsServer{
-		sqlConfig: sqlCfg,
-	}
-	RegisterMetricsCollectorServer(s.handler, &metricsServer)
+	metricsServer := New(k8sCfg, sqlCfg)
+	RegisterMetricsCollectorServer(s.handler, metricsServer)
 
 	// Start grpc server
 	if err := s.handler.Serve(lis); err != nil {
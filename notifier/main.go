@@ -5,23 +5,34 @@ import (
 	"github.com/gofiber/fiber/v2"
 )
 
-var events chan bool
+var events chan Event
 
+// Event of a benchmark run starting or stopping. Id is the run id the driver passes as the `id`
+// query parameter, so the runner can tell the runs of drivers benchmarked in parallel apart
 type Event struct {
-	Status bool `json:"status"`
+	Status bool   `json:"status"`
+	Id     *int64 `json:"id,omitempty"`
+}
+
+func runId(c *fiber.Ctx) *int64 {
+	id := int64(c.QueryInt("id", -1))
+	if id < 0 {
+		return nil
+	}
+	return &id
 }
 
 func main() {
 	app := fiber.New()
-	events = make(chan bool)
+	events = make(chan Event)
 
 	app.Post("/starting", func(c *fiber.Ctx) error {
-		events <- true
+		events <- Event{Status: true, Id: runId(c)}
 		return c.SendStatus(200)
 	})
 
 	app.Post("/stopping", func(c *fiber.Ctx) error {
-		events <- false
+		events <- Event{Status: false, Id: runId(c)}
 		return c.SendStatus(200)
 	})
 
@@ -35,10 +46,7 @@ func main() {
 
 	app.Get("/ws", websocket.New(func(c *websocket.Conn) {
 		for {
-			status := <-events
-			c.WriteJSON(Event{
-				Status: status,
-			})
+			c.WriteJSON(<-events)
 		}
 	}))
 
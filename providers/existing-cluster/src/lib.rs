@@ -0,0 +1,46 @@
+use std::net::{IpAddr, Ipv4Addr};
+
+use anyhow::Result;
+use common::{config::SetupArgs, provider::*};
+
+/// A kubernetes cluster that already exists, set by `kubernetes.kubeconfig`. Nothing is created or
+/// destroyed, and there are no nodes to connect to: the runner reaches the services it needs
+/// through port-forwards on the local machine
+pub struct ExistingCluster;
+
+#[async_trait::async_trait]
+impl Platform for ExistingCluster {
+    async fn pre_setup(&self, _: &SetupArgs, _: bool) -> Result<()> {
+        Ok(())
+    }
+
+    async fn setup(&self, _: &SetupArgs, _: bool) -> Result<()> {
+        Ok(())
+    }
+
+    async fn platform_info(&self, _: &SetupArgs, _: bool) -> Result<PlatformInfo> {
+        Ok(PlatformInfo {
+            master_ip: IpAddr::V4(Ipv4Addr::LOCALHOST),
+            worker_ips: Vec::new(),
+            ssh_key: String::new(),
+            host_username: None,
+        })
+    }
+
+    async fn destroy(&self, _: &SetupArgs, _: bool) -> Result<()> {
+        println!("Existing cluster is left as it is");
+        Ok(())
+    }
+
+    fn name(&self) -> String {
+        "existing".to_owned()
+    }
+
+    fn uses_platform_dir(&self) -> bool {
+        false
+    }
+
+    fn provides_cluster(&self) -> bool {
+        true
+    }
+}
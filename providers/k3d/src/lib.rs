@@ -0,0 +1,254 @@
+use std::{
+    collections::HashMap,
+    net::{IpAddr, Ipv4Addr},
+    process::Command,
+};
+
+use anyhow::Result;
+use common::{
+    command::command_print,
+    config::SetupArgs,
+    exit,
+    provider::{Platform, PlatformInfo, DESTROY, SETUP},
+};
+use tokio::fs;
+
+/// Name of the k3d cluster, its nodes are the `k3d-graph-benchmarks-*` containers
+const CLUSTER: &str = "graph-benchmarks";
+/// Registry the images are pushed to, k3d names its container `k3d-graph-benchmarks-registry`
+const REGISTRY: &str = "k3d-graph-benchmarks-registry";
+const DEFAULT_REGISTRY_PORT: &str = "5111";
+/// Files written next to the other kubeconfigs, in the `k3s` directory
+const DIR: &str = "k3s";
+const KUBE_CONFIG: &str = "k3d-kube-config";
+const REGISTRIES: &str = "k3d-registries.yaml";
+
+/// Runs a k3s cluster in docker containers of the local machine with
+/// [k3d](https://k3d.io), along with a registry for the images, so the whole pipeline runs on a
+/// single Linux box without VMs. The cluster is then benchmarked like an existing cluster, through
+/// the kubeconfig written to `k3s/k3d-kube-config`
+pub struct K3d;
+
+#[async_trait::async_trait]
+impl Platform for K3d {
+    async fn pre_setup(&self, setup_args: &SetupArgs, _: bool) -> Result<()> {
+        // the images are pushed to localhost, which the nodes pull from the registry container
+        let port = registry_port(setup_args);
+        fs::write(
+            format!("{DIR}/{REGISTRIES}"),
+            format!(
+                "mirrors:\n  \"localhost:{port}\":\n    endpoint:\n      - http://{REGISTRY}:5000\n"
+            ),
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn setup(&self, setup_args: &SetupArgs, verbose: bool) -> Result<()> {
+        let port = registry_port(setup_args);
+        if !docker(&["inspect", REGISTRY])?.status.success() {
+            command_print(
+                "k3d",
+                &[
+                    "registry",
+                    "create",
+                    REGISTRY.trim_start_matches("k3d-"),
+                    "--port",
+                    &format!("127.0.0.1:{port}"),
+                ],
+                verbose,
+                [
+                    "Creating image registry",
+                    "Could not create image registry",
+                    "Image registry created",
+                ],
+                DIR,
+                HashMap::<&str, &str>::new(),
+            )
+            .await?;
+        }
+
+        if !k3d(&["cluster", "get", CLUSTER])?.status.success() {
+            // the server is the first node, the agents are the others
+            let agents = match setup_args
+                .platform_args
+                .as_ref()
+                .and_then(|x| x.get("agents"))
+            {
+                Some(agents) => agents.to_owned(),
+                None => (setup_args.node_configs.iter().max().unwrap() - 1).to_string(),
+            };
+            let registry = format!("{REGISTRY}:{port}");
+            let mut args = vec![
+                "cluster",
+                "create",
+                CLUSTER,
+                "--agents",
+                &agents,
+                "--registry-use",
+                &registry,
+                "--registry-config",
+                REGISTRIES,
+                "--kubeconfig-update-default=false",
+                "--kubeconfig-switch-context=false",
+                "--wait",
+            ];
+            let image = setup_args
+                .platform_args
+                .as_ref()
+                .and_then(|x| x.get("image"));
+            if let Some(image) = image {
+                args.extend(["--image", image]);
+            }
+            let limits = [
+                ("--servers-memory", &setup_args.master_platform),
+                ("--agents-memory", &setup_args.worker_platform),
+            ]
+            .into_iter()
+            .filter_map(|(flag, resources)| {
+                let memory = resources.as_ref()?.get("memory")?;
+                Some((flag, format!("{memory}m")))
+            })
+            .collect::<Vec<_>>();
+            for (flag, memory) in &limits {
+                args.extend([*flag, memory.as_str()]);
+            }
+            command_print(
+                "k3d",
+                &args,
+                verbose,
+                SETUP,
+                DIR,
+                HashMap::<&str, &str>::new(),
+            )
+            .await?;
+        }
+
+        command_print(
+            "k3d",
+            &["kubeconfig", "write", CLUSTER, "--output", KUBE_CONFIG],
+            verbose,
+            [
+                "Writing cluster kubeconfig",
+                "Could not write cluster kubeconfig",
+                &format!("Cluster kubeconfig written to {DIR}/{KUBE_CONFIG}"),
+            ],
+            DIR,
+            HashMap::<&str, &str>::new(),
+        )
+        .await
+    }
+
+    async fn platform_info(&self, _: &SetupArgs, _: bool) -> Result<PlatformInfo> {
+        let agents = docker(&[
+            "ps",
+            "--filter",
+            &format!("label=k3d.cluster={CLUSTER}"),
+            "--filter",
+            "label=k3d.role=agent",
+            "--format",
+            "{{.Names}}",
+        ])?;
+        if !agents.status.success() {
+            exit!(
+                String::from_utf8_lossy(&agents.stderr).to_string(),
+                "Could not list the nodes of cluster {}, is docker running?",
+                CLUSTER
+            );
+        }
+        let names = String::from_utf8(agents.stdout)?
+            .lines()
+            .map(|x| x.trim().to_owned())
+            .filter(|x| !x.is_empty())
+            .collect::<Vec<_>>();
+
+        let mut worker_ips = Vec::new();
+        if !names.is_empty() {
+            let format =
+                format!("{{{{(index .NetworkSettings.Networks \"k3d-{CLUSTER}\").IPAddress}}}}");
+            let mut args = vec!["inspect", "-f", &format];
+            args.extend(names.iter().map(|x| x.as_str()));
+            let output = docker(&args)?;
+            if !output.status.success() {
+                exit!(
+                    String::from_utf8_lossy(&output.stderr).to_string(),
+                    "Could not get node IPs"
+                );
+            }
+            for ip in String::from_utf8(output.stdout)?.lines() {
+                worker_ips.push(ip.trim().parse::<IpAddr>()?);
+            }
+        }
+
+        // as on an existing cluster, the runner reaches its services through port-forwards
+        Ok(PlatformInfo {
+            master_ip: IpAddr::V4(Ipv4Addr::LOCALHOST),
+            worker_ips,
+            ssh_key: String::new(),
+            host_username: None,
+        })
+    }
+
+    async fn destroy(&self, _: &SetupArgs, verbose: bool) -> Result<()> {
+        if k3d(&["cluster", "get", CLUSTER])?.status.success() {
+            command_print(
+                "k3d",
+                &["cluster", "delete", CLUSTER],
+                verbose,
+                DESTROY,
+                DIR,
+                HashMap::<&str, &str>::new(),
+            )
+            .await?;
+        }
+
+        if docker(&["inspect", REGISTRY])?.status.success() {
+            command_print(
+                "k3d",
+                &["registry", "delete", REGISTRY],
+                verbose,
+                [
+                    "Removing image registry",
+                    "Could not remove image registry",
+                    "Image registry removed",
+                ],
+                DIR,
+                HashMap::<&str, &str>::new(),
+            )
+            .await?;
+        }
+        Ok(())
+    }
+
+    fn name(&self) -> String {
+        "k3d".to_owned()
+    }
+
+    fn uses_platform_dir(&self) -> bool {
+        false
+    }
+
+    fn provides_cluster(&self) -> bool {
+        true
+    }
+}
+
+/// Local port of the registry, can be set with the `registry_port` platform argument
+fn registry_port(setup_args: &SetupArgs) -> String {
+    setup_args
+        .platform_args
+        .as_ref()
+        .and_then(|x| x.get("registry_port").cloned())
+        .unwrap_or(DEFAULT_REGISTRY_PORT.to_owned())
+}
+
+fn k3d(args: &[&str]) -> Result<std::process::Output> {
+    match Command::new("k3d").args(args).output() {
+        Ok(o) => Ok(o),
+        Err(err) => exit!(err, "Could not run k3d, is it installed?"),
+    }
+}
+
+fn docker(args: &[&str]) -> Result<std::process::Output> {
+    Ok(Command::new("docker").args(args).output()?)
+}
@@ -0,0 +1,172 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    net::IpAddr,
+    path::Path,
+};
+
+use anyhow::Result;
+use common::{command::command_print, config::SetupArgs, exit, provider::*};
+use serde::Serialize;
+use tokio::fs;
+use tracing::info;
+
+#[derive(Debug, Clone, Serialize)]
+struct InventoryGroup {
+    hosts: BTreeMap<String, ()>,
+    vars: BTreeMap<&'static str, String>,
+}
+
+/// Machines that already exist, listed in `[setup.platform_args]`:
+/// - `master_ip`: address of the master node
+/// - `worker_ips`: comma separated addresses of the worker nodes
+/// - `ssh_key`: private key to connect to every node with
+/// - `username`: optional user to connect as, instead of `setup.host_username`
+/// - `uninstall_k3s`: optional, `true` to uninstall k3s from the nodes on destroy
+///
+/// Nothing is created or destroyed, besides the software installed by the setup
+pub struct StaticHosts;
+
+#[async_trait::async_trait]
+impl Platform for StaticHosts {
+    async fn pre_setup(&self, setup_args: &SetupArgs, _: bool) -> Result<()> {
+        let info = hosts(setup_args)?;
+        if !Path::new(&info.ssh_key).exists() {
+            exit!(
+                "setup.platform_args.ssh_key",
+                "SSH key {} does not exist",
+                info.ssh_key
+            );
+        }
+
+        let nodes = info.worker_ips.len() + 1;
+        if let Some(n) = setup_args.node_configs.iter().find(|x| **x > nodes) {
+            exit!(
+                "setup.node_configs",
+                "Node configuration {} needs more than the {} listed hosts",
+                n,
+                nodes
+            );
+        }
+        Ok(())
+    }
+
+    async fn setup(&self, setup_args: &SetupArgs, _: bool) -> Result<()> {
+        let info = hosts(setup_args)?;
+        info!(
+            "using existing hosts, master {} & workers {:?}",
+            info.master_ip, info.worker_ips
+        );
+        Ok(())
+    }
+
+    async fn platform_info(&self, setup_args: &SetupArgs, _: bool) -> Result<PlatformInfo> {
+        hosts(setup_args)
+    }
+
+    async fn destroy(&self, setup_args: &SetupArgs, verbose: bool) -> Result<()> {
+        if arg(setup_args, "uninstall_k3s").as_deref() != Some("true") {
+            println!(
+                "Static hosts are left as they are, set uninstall_k3s to remove k3s from them"
+            );
+            return Ok(());
+        }
+
+        let info = hosts(setup_args)?;
+        let vars = BTreeMap::from([("ansible_user", info.username(setup_args))]);
+        let inventory = HashMap::from([
+            (
+                "master",
+                InventoryGroup {
+                    hosts: BTreeMap::from([(info.master_ip.to_string(), ())]),
+                    vars: vars.clone(),
+                },
+            ),
+            (
+                "workers",
+                InventoryGroup {
+                    hosts: info
+                        .worker_ips
+                        .iter()
+                        .map(|x| (x.to_string(), ()))
+                        .collect(),
+                    vars,
+                },
+            ),
+        ]);
+        fs::write(
+            "k3s/inventory/static-hosts.yaml",
+            serde_yaml::to_string(&inventory)?,
+        )
+        .await?;
+
+        for (playbook, node) in [
+            ("uninstall-worker.yaml", "worker"),
+            ("uninstall-master.yaml", "master"),
+        ] {
+            command_print(
+                "ansible-playbook",
+                &[
+                    playbook,
+                    "--private-key",
+                    &info.ssh_key,
+                    "-i",
+                    "inventory/static-hosts.yaml",
+                ],
+                verbose,
+                [
+                    &format!("Uninstalling k3s from {node} nodes"),
+                    &format!("Could not uninstall k3s from {node} nodes"),
+                    &format!("Uninstalled k3s from {node} nodes"),
+                ],
+                "k3s",
+                HashMap::from([("ANSIBLE_HOST_KEY_CHECKING", "False")]),
+            )
+            .await?;
+        }
+        Ok(())
+    }
+
+    fn name(&self) -> String {
+        "static".to_owned()
+    }
+
+    fn uses_platform_dir(&self) -> bool {
+        false
+    }
+}
+
+fn arg(setup_args: &SetupArgs, key: &str) -> Option<String> {
+    setup_args
+        .platform_args
+        .as_ref()
+        .and_then(|x| x.get(key).map(|x| x.trim().to_owned()))
+}
+
+/// Connection information of the hosts listed in the platform arguments
+fn hosts(setup_args: &SetupArgs) -> Result<PlatformInfo> {
+    let required = |key: &str| match arg(setup_args, key) {
+        Some(x) if !x.is_empty() => Ok(x),
+        _ => exit!(
+            format!("setup.platform_args.{key}"),
+            "The static provider needs {} in [setup.platform_args]",
+            key
+        ),
+    };
+    let parse = |key: &str, ip: &str| match ip.trim().parse::<IpAddr>() {
+        Ok(ip) => Ok(ip),
+        Err(err) => exit!(err, "Invalid address {} in setup.platform_args.{}", ip, key),
+    };
+
+    let master_ip = parse("master_ip", &required("master_ip")?)?;
+    let worker_ips = required("worker_ips")?
+        .split(',')
+        .filter(|x| !x.trim().is_empty())
+        .map(|x| parse("worker_ips", x))
+        .collect::<Result<Vec<_>>>()?;
+    Ok(PlatformInfo {
+        master_ip,
+        worker_ips,
+        ssh_key: required("ssh_key")?,
+        host_username: arg(setup_args, "username"),
+    })
+}
@@ -94,6 +94,7 @@ impl Platform for Terraform {
             master_ip: ips[0],
             worker_ips: ips.into_iter().skip(1).collect(),
             ssh_key: private_key_file,
+            host_username: None,
         })
     }
 
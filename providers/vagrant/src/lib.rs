@@ -175,6 +175,7 @@ impl Platform for Vagrant {
             worker_ips,
             master_ip,
             ssh_key: home.join(".ssh/id_rsa").to_str().unwrap().to_owned(),
+            host_username: None,
         })
     }
 